@@ -0,0 +1,60 @@
+use std::collections::VecDeque;
+use std::time::Duration;
+
+// How many entries to keep around; older ones are dropped
+const MAX_ENTRIES: usize = 50;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ActivityKind {
+    Round,
+    Player,
+    Bot,
+    Chatter,
+    Death,
+}
+
+#[derive(Debug, Clone)]
+pub struct ActivityEntry {
+    pub message: String,
+    pub kind: ActivityKind,
+    pub round_time: Duration, // how far into the round it happened
+    pub miner: Option<usize>, // miner index it's about (0 = player, i + 1 = bots[i]), for their color
+}
+
+// Record of things that actually happened this game, newest last
+#[derive(Debug, Default)]
+pub struct ActivityLog {
+    entries: VecDeque<ActivityEntry>,
+}
+
+impl ActivityLog {
+    pub fn new() -> Self {
+        ActivityLog::default()
+    }
+
+    // `round_time` is the round's elapsed time at the moment of the event
+    pub fn push(&mut self, kind: ActivityKind, message: String, round_time: Duration) {
+        self.push_entry(ActivityEntry { message, kind, round_time, miner: None });
+    }
+
+    // Same as push, for an event about one particular miner
+    pub fn push_miner(&mut self, miner_index: usize, kind: ActivityKind, message: String, round_time: Duration) {
+        self.push_entry(ActivityEntry { message, kind, round_time, miner: Some(miner_index) });
+    }
+
+    fn push_entry(&mut self, entry: ActivityEntry) {
+        if self.entries.len() >= MAX_ENTRIES {
+            self.entries.pop_front();
+        }
+        self.entries.push_back(entry);
+    }
+
+    // Most recent entries first
+    pub fn recent(&self, count: usize) -> impl Iterator<Item = &ActivityEntry> {
+        self.entries.iter().rev().take(count)
+    }
+
+    pub fn clear(&mut self) {
+        self.entries.clear();
+    }
+}
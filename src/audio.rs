@@ -0,0 +1,139 @@
+use ggez::Context;
+use ggez::audio::{SoundSource, Source};
+
+use crate::settings::Settings;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Sound {
+    Upgrade,
+    Donate,
+    RoundEnd,
+    PetLoot,
+    Victory,
+}
+
+impl Sound {
+    fn path(&self) -> &'static str {
+        match self {
+            Sound::Upgrade => "/sounds/upgrade.wav",
+            Sound::Donate => "/sounds/donate.wav",
+            Sound::RoundEnd => "/sounds/round_end.wav",
+            Sound::PetLoot => "/sounds/pet_loot.wav",
+            Sound::Victory => "/sounds/victory.wav",
+        }
+    }
+}
+
+const MUSIC_PATH: &str = "/sounds/music.wav";
+
+// Owns every sound source. Game logic queues sounds and they're all played from
+// `play_queued`, so volume is applied in exactly one place.
+pub struct Audio {
+    upgrade: Option<Source>,
+    donate: Option<Source>,
+    round_end: Option<Source>,
+    pet_loot: Option<Source>,
+    victory: Option<Source>,
+    music: Option<Source>,
+    queued: Vec<Sound>,
+}
+
+impl Audio {
+    pub fn new(ctx: &mut Context, settings: &Settings) -> Self {
+        let mut audio = Audio {
+            upgrade: Self::load(ctx, Sound::Upgrade.path()),
+            donate: Self::load(ctx, Sound::Donate.path()),
+            round_end: Self::load(ctx, Sound::RoundEnd.path()),
+            pet_loot: Self::load(ctx, Sound::PetLoot.path()),
+            victory: Self::load(ctx, Sound::Victory.path()),
+            music: Self::load(ctx, MUSIC_PATH),
+            queued: Vec::new(),
+        };
+
+        if let Some(music) = &mut audio.music {
+            music.set_repeat(true);
+            music.set_volume(settings.effective_music_volume());
+            if let Err(e) = music.play(ctx) {
+                eprintln!("Could not start music: {}", e);
+            }
+        }
+
+        audio
+    }
+
+    // No sources at all, for games run without a window (tests, balance reports)
+    pub fn silent() -> Self {
+        Audio {
+            upgrade: None,
+            donate: None,
+            round_end: None,
+            pet_loot: None,
+            victory: None,
+            music: None,
+            queued: Vec::new(),
+        }
+    }
+
+    // Missing sound files just mean silence, not a crash
+    fn load(ctx: &mut Context, path: &str) -> Option<Source> {
+        match Source::new(ctx, path) {
+            Ok(source) => Some(source),
+            Err(e) => {
+                eprintln!("Could not load sound {}: {}", path, e);
+                None
+            }
+        }
+    }
+
+    pub fn queue(&mut self, sound: Sound) {
+        self.queued.push(sound);
+    }
+
+    pub fn play_queued(&mut self, ctx: &mut Context, settings: &Settings) {
+        let volume = settings.effective_sfx_volume();
+
+        for sound in std::mem::take(&mut self.queued) {
+            // Zero volume skips playback entirely; the audio engine keeps running
+            if volume <= 0.0 {
+                continue;
+            }
+
+            let source = match sound {
+                Sound::Upgrade => &mut self.upgrade,
+                Sound::Donate => &mut self.donate,
+                Sound::RoundEnd => &mut self.round_end,
+                Sound::PetLoot => &mut self.pet_loot,
+                Sound::Victory => &mut self.victory,
+            };
+
+            if let Some(source) = source {
+                source.set_volume(volume);
+                if let Err(e) = source.play_detached(ctx) {
+                    eprintln!("Could not play sound: {}", e);
+                }
+            }
+        }
+    }
+
+    // Call whenever the volume settings change
+    pub fn apply_volume(&mut self, settings: &Settings) {
+        if let Some(music) = &mut self.music {
+            music.set_volume(settings.effective_music_volume());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn every_sound_ships_with_the_game() {
+        let resources = std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("resources");
+        let paths = [Sound::Upgrade, Sound::Donate, Sound::RoundEnd, Sound::PetLoot, Sound::Victory]
+            .map(|sound| sound.path());
+        for path in paths.iter().chain([&MUSIC_PATH]) {
+            assert!(resources.join(path.trim_start_matches('/')).is_file(), "missing {}", path);
+        }
+    }
+}
@@ -0,0 +1,93 @@
+use crate::config::GameConfig;
+use crate::game_state::{GameState, MainState};
+use crate::miner::BotStrategy;
+
+// One strategy's results over a balance report
+struct StrategyTally {
+    strategy: BotStrategy,
+    wins: usize,
+    bots_played: usize, // a strategy can fill several slots, so this can exceed the game count
+    final_health: i32, // summed over every bot that played it; dead bots add 0
+}
+
+// Plays `games` seeded, headless games between the bots alone and prints each strategy's
+// wins and average final health. Games run turn-based so each finishes instantly; the
+// rest of `config` (bots, difficulty, rounds, ...) is used as given.
+pub fn run_report(config: GameConfig, games: usize) {
+    let config = GameConfig { turn_based: true, ..config };
+    let mut tallies: Vec<StrategyTally> = Vec::new();
+
+    for seed in 0..games as u64 {
+        let state = play_bots_only(config, seed);
+        let winner = winner(&state);
+
+        for (i, bot) in state.bots.iter().enumerate() {
+            let strategy = BotStrategy::for_index(i);
+            let tally = match tallies.iter().position(|tally| tally.strategy == strategy) {
+                Some(index) => &mut tallies[index],
+                None => {
+                    tallies.push(StrategyTally { strategy, wins: 0, bots_played: 0, final_health: 0 });
+                    tallies.last_mut().unwrap()
+                }
+            };
+            tally.bots_played += 1;
+            tally.final_health += bot.health.max(0);
+            if winner == Some(i) {
+                tally.wins += 1;
+            }
+        }
+    }
+
+    println!(
+        "Balance report: {} games, {} bots, {} difficulty, {} rounds",
+        games,
+        config.bot_count,
+        config.difficulty.label(),
+        config.round_count
+    );
+    println!("{:<10} {:>6} {:>7} {:>11}", "Strategy", "Wins", "Win %", "Avg health");
+    for tally in &tallies {
+        println!(
+            "{:<10} {:>6} {:>6.1}% {:>11.2}",
+            tally.strategy.name(),
+            tally.wins,
+            tally.wins as f32 / games.max(1) as f32 * 100.0,
+            tally.final_health as f32 / tally.bots_played.max(1) as f32
+        );
+    }
+}
+
+// Runs one game to the end with the player sitting out, so only the bots are ranked
+fn play_bots_only(config: GameConfig, seed: u64) -> MainState {
+    let mut state = MainState::headless(config, seed);
+    state.player.alive = false;
+    state.player.health = 0;
+
+    // The nemesis only exists to beat the player, so every slot keeps its own strategy
+    for (i, bot) in state.bots.iter_mut().enumerate() {
+        bot.strategy = Some(BotStrategy::for_index(i));
+    }
+
+    loop {
+        match state.game_state {
+            GameState::Playing => state.end_turn(),
+            GameState::RoundEnd => state.start_next_round(),
+            _ => return state,
+        }
+    }
+}
+
+// The game's winner by bot index: the healthiest survivor, then the bigger total donor
+fn winner(state: &MainState) -> Option<usize> {
+    state
+        .bots
+        .iter()
+        .enumerate()
+        .filter(|(_, bot)| bot.alive)
+        .max_by(|(_, a), (_, b)| {
+            a.health
+                .cmp(&b.health)
+                .then(a.total_donated.partial_cmp(&b.total_donated).unwrap())
+        })
+        .map(|(i, _)| i)
+}
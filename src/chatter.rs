@@ -0,0 +1,84 @@
+use crate::miner::BotStrategy;
+
+// Flavor lines bots say into the activity log. `{}` is replaced with the bot's name.
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ChatterEvent {
+    Upgrade,
+    Donate,
+}
+
+pub fn lines(strategy: BotStrategy, event: ChatterEvent) -> &'static [&'static str] {
+    match (strategy, event) {
+        (BotStrategy::Economy, ChatterEvent::Upgrade) => &[
+            "Bot {} reinvests. Compound interest!",
+            "Bot {} is hoarding for the long game...",
+        ],
+        (BotStrategy::Economy, ChatterEvent::Donate) => &[
+            "Bot {} grudgingly parts with a few coins.",
+            "Bot {} donates the bare minimum.",
+        ],
+        (BotStrategy::Aggressive, ChatterEvent::Upgrade) => &[
+            "Bot {}: \"Bigger pickaxe, bigger swings!\"",
+            "Bot {} can't wait to spend it all.",
+        ],
+        (BotStrategy::Aggressive, ChatterEvent::Donate) => &[
+            "Bot {} dumps everything!",
+            "Bot {}: \"Top spot is mine!\"",
+        ],
+        (BotStrategy::Balanced, ChatterEvent::Upgrade) => &[
+            "Bot {} makes a sensible upgrade.",
+            "Bot {} keeps things even.",
+        ],
+        (BotStrategy::Balanced, ChatterEvent::Donate) => &[
+            "Bot {} donates a measured amount.",
+            "Bot {}: \"Slow and steady.\"",
+        ],
+        (BotStrategy::Usurper, ChatterEvent::Upgrade) => &[
+            "Bot {} sharpens its pickaxe and watches the leader.",
+            "Bot {} bides its time.",
+        ],
+        (BotStrategy::Usurper, ChatterEvent::Donate) => &[
+            "Bot {}: \"Nobody stays on top for long.\"",
+            "Bot {} goes after the crown!",
+        ],
+        (BotStrategy::Survivor, ChatterEvent::Upgrade) => &[
+            "Bot {} invests, carefully.",
+            "Bot {}: \"Slow and steady.\"",
+        ],
+        (BotStrategy::Survivor, ChatterEvent::Donate) => &[
+            "Bot {}: \"Just enough. Not a coin more.\"",
+            "Bot {} scrapes out of last place.",
+        ],
+        (BotStrategy::Random, ChatterEvent::Upgrade) => &[
+            "Bot {} upgrades on a whim.",
+            "Bot {} flips a coin... upgrade!",
+        ],
+        (BotStrategy::Random, ChatterEvent::Donate) => &[
+            "Bot {} tosses some gold in. Who knows why.",
+            "Bot {}: \"Surprise!\"",
+        ],
+        (BotStrategy::Nemesis, ChatterEvent::Upgrade) => &[
+            "Bot {}: \"Whatever you mine, I'll mine more.\"",
+            "Bot {} is watching your purse.",
+        ],
+        (BotStrategy::Nemesis, ChatterEvent::Donate) => &[
+            "Bot {}: \"Saw your donation. Cute.\"",
+            "Bot {} tops your donation, just because.",
+        ],
+    }
+}
+
+// What the nemesis says at the end of a round it finished above the player
+pub const NEMESIS_TAUNTS: &[&str] = &[
+    "Bot {}: \"Ahead of you again.\"",
+    "Bot {}: \"Did that hurt?\"",
+    "Bot {}: \"You'll never outgive me.\"",
+];
+
+// ...and one where the player finished above it
+pub const NEMESIS_GRUMBLES: &[&str] = &[
+    "Bot {}: \"Enjoy it while it lasts.\"",
+    "Bot {}: \"Lucky round.\"",
+    "Bot {}: \"I'll remember this.\"",
+];
@@ -0,0 +1,305 @@
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::time::Duration;
+
+use crate::game_state::{MAX_ROUNDS, ROUND_DURATION};
+use crate::miner::{CurveParams, CurveShape, YieldCurve, DEEP_UPGRADE_LEVEL, MAX_UPGRADE_LEVEL, STARTING_HEALTH};
+
+// Options chosen on the start menu. They stay the same across restarts
+// until the player changes them again.
+
+pub const MIN_BOTS: usize = 1;
+pub const MAX_BOTS: usize = 5;
+pub const DEFAULT_BOTS: usize = 3;
+pub const ROUND_LENGTH_OPTIONS: [u64; 4] = [15, 30, 45, 60]; // seconds
+pub const DONATION_LOCK_OPTIONS: [u64; 3] = [0, 3, 5]; // seconds before the round ends; 0 turns the lock off
+pub const HEALTH_OPTIONS: [i32; 5] = [3, 5, 10, 15, 20]; // starting and max health for everyone
+
+// Optional custom yield curve, key=value like the settings file. It shows up on the menu
+// as Custom after the presets when it's there and valid.
+pub const YIELD_CURVE_FILE: &str = "yield.cfg";
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum Difficulty {
+    Easy,
+    Normal,
+    Hard,
+}
+
+impl Difficulty {
+    pub fn label(&self) -> &'static str {
+        match self {
+            Difficulty::Easy => "Easy",
+            Difficulty::Normal => "Normal",
+            Difficulty::Hard => "Hard",
+        }
+    }
+
+    // Parses a name like "hard", ignoring case
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.to_ascii_lowercase().as_str() {
+            "easy" => Some(Difficulty::Easy),
+            "normal" => Some(Difficulty::Normal),
+            "hard" => Some(Difficulty::Hard),
+            _ => None,
+        }
+    }
+
+    pub fn next(&self) -> Self {
+        match self {
+            Difficulty::Easy => Difficulty::Normal,
+            Difficulty::Normal => Difficulty::Hard,
+            Difficulty::Hard => Difficulty::Easy,
+        }
+    }
+
+    pub fn previous(&self) -> Self {
+        match self {
+            Difficulty::Easy => Difficulty::Hard,
+            Difficulty::Normal => Difficulty::Easy,
+            Difficulty::Hard => Difficulty::Normal,
+        }
+    }
+
+    // Multiplier on how much of their gold bots donate
+    pub fn bot_donation_factor(&self) -> f32 {
+        match self {
+            Difficulty::Easy => 0.75,
+            Difficulty::Normal => 1.0,
+            Difficulty::Hard => 1.25,
+        }
+    }
+
+    // Gold it takes to unlock the pet companion
+    pub fn pet_unlock_cost(&self) -> f32 {
+        match self {
+            Difficulty::Easy => 750.0,
+            Difficulty::Normal => 1000.0,
+            Difficulty::Hard => 1500.0,
+        }
+    }
+}
+
+// Who gets hurt at the end of a round, by finishing position (0 = first place)
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum DamageModel {
+    Linear,        // Everyone but first place, more the further down they finish
+    LastPlaceOnly, // Only the last place takes damage
+    TopHalfSafe,   // The top half is spared, the rest take linear damage
+}
+
+impl DamageModel {
+    pub fn label(&self) -> &'static str {
+        match self {
+            DamageModel::Linear => "Linear",
+            DamageModel::LastPlaceOnly => "Last Place",
+            DamageModel::TopHalfSafe => "Top Half Safe",
+        }
+    }
+
+    pub fn next(&self) -> Self {
+        match self {
+            DamageModel::Linear => DamageModel::LastPlaceOnly,
+            DamageModel::LastPlaceOnly => DamageModel::TopHalfSafe,
+            DamageModel::TopHalfSafe => DamageModel::Linear,
+        }
+    }
+
+    pub fn previous(&self) -> Self {
+        match self {
+            DamageModel::Linear => DamageModel::TopHalfSafe,
+            DamageModel::LastPlaceOnly => DamageModel::Linear,
+            DamageModel::TopHalfSafe => DamageModel::LastPlaceOnly,
+        }
+    }
+
+    // Who takes damage under this model, for the gameplay tooltips
+    pub fn description(&self) -> &'static str {
+        match self {
+            DamageModel::Linear => "1st place takes no damage, 2nd takes 1, and so on.",
+            DamageModel::LastPlaceOnly => "Only last place takes damage:\n1 for each place below 1st.",
+            DamageModel::TopHalfSafe => "The top half takes no damage, the rest\ntake 1 for each place below 1st.",
+        }
+    }
+
+    // Damage for finishing at `position` out of `miner_count` ranked miners.
+    // Whoever is hit still takes their linear amount, the model only picks who.
+    pub fn damage_for(&self, position: usize, miner_count: usize) -> i32 {
+        let hit = match self {
+            DamageModel::Linear => true,
+            DamageModel::LastPlaceOnly => position + 1 == miner_count,
+            DamageModel::TopHalfSafe => position >= miner_count.div_ceil(2),
+        };
+
+        if hit { position as i32 } else { 0 }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct GameConfig {
+    pub bot_count: usize,
+    pub difficulty: Difficulty,
+    pub round_duration: Duration,
+    pub damage_model: DamageModel,
+    pub round_count: usize, // rounds in a game; only changeable from the command line
+    pub donation_lock: Duration, // final stretch of the round where bots stop donating; zero for no lock
+    pub adaptive_difficulty: bool, // bots ease off when the player struggles and push harder when they dominate
+    pub first_round_shield: bool, // nobody takes damage in round 1; follows Easy unless changed on the menu
+    #[serde(default)]
+    pub free_pet: bool, // the pet starts unlocked instead of costing gold
+    #[serde(default)]
+    pub sandbox: bool, // practice mode: bots never donate, and the game isn't counted in the lifetime stats
+    #[serde(default)]
+    pub turn_based: bool, // no round clock; each round ends when the player ends their turn
+    #[serde(default)]
+    pub bot_forecast: bool, // assist: the opponents panel estimates what each bot will donate by round end
+    #[serde(default = "default_starting_health")]
+    pub starting_health: i32, // everyone's starting and max health
+    #[serde(default)]
+    pub hardcore: bool, // permadeath: a lost run can't be restarted, and it's recorded apart from normal games
+    #[serde(default)]
+    pub yield_curve: YieldCurve, // how mining income grows with the pickaxe and mine levels
+    #[serde(default)]
+    pub deep_upgrades: bool, // upgrades go up to DEEP_UPGRADE_LEVEL; only changeable from the command line
+}
+
+impl Default for GameConfig {
+    fn default() -> Self {
+        GameConfig {
+            bot_count: DEFAULT_BOTS,
+            difficulty: Difficulty::Normal,
+            round_duration: ROUND_DURATION,
+            damage_model: DamageModel::Linear,
+            round_count: MAX_ROUNDS,
+            donation_lock: Duration::ZERO,
+            adaptive_difficulty: false,
+            first_round_shield: false,
+            free_pet: false,
+            sandbox: false,
+            turn_based: false,
+            bot_forecast: false,
+            hardcore: false,
+            starting_health: STARTING_HEALTH,
+            yield_curve: YieldCurve::Classic,
+            deep_upgrades: false,
+        }
+    }
+}
+
+// Saves from before health could be picked always started at the standard amount
+fn default_starting_health() -> i32 {
+    STARTING_HEALTH
+}
+
+impl GameConfig {
+    // Highest level the pickaxe and mine can reach this game
+    pub fn upgrade_cap(&self) -> usize {
+        if self.deep_upgrades { DEEP_UPGRADE_LEVEL } else { MAX_UPGRADE_LEVEL }
+    }
+
+    // Steps the round length through ROUND_LENGTH_OPTIONS, wrapping at either end
+    pub fn cycle_round_length(&mut self, forward: bool) {
+        let current = self.round_duration.as_secs();
+        let index = ROUND_LENGTH_OPTIONS.iter().position(|&secs| secs == current).unwrap_or(1);
+        let count = ROUND_LENGTH_OPTIONS.len();
+        let next = if forward { (index + 1) % count } else { (index + count - 1) % count };
+
+        self.round_duration = Duration::from_secs(ROUND_LENGTH_OPTIONS[next]);
+    }
+
+    // Steps the donation lock through DONATION_LOCK_OPTIONS, wrapping at either end
+    pub fn cycle_donation_lock(&mut self, forward: bool) {
+        let current = self.donation_lock.as_secs();
+        let index = DONATION_LOCK_OPTIONS.iter().position(|&secs| secs == current).unwrap_or(0);
+        let count = DONATION_LOCK_OPTIONS.len();
+        let next = if forward { (index + 1) % count } else { (index + count - 1) % count };
+
+        self.donation_lock = Duration::from_secs(DONATION_LOCK_OPTIONS[next]);
+    }
+
+    // Steps the starting health through HEALTH_OPTIONS, wrapping at either end
+    pub fn cycle_starting_health(&mut self, forward: bool) {
+        let index = HEALTH_OPTIONS.iter().position(|&health| health == self.starting_health).unwrap_or(0);
+        let count = HEALTH_OPTIONS.len();
+        let next = if forward { (index + 1) % count } else { (index + count - 1) % count };
+
+        self.starting_health = HEALTH_OPTIONS[next];
+    }
+
+    // Steps through the yield curve presets, then the custom curve from YIELD_CURVE_FILE
+    // if there's a valid one, wrapping at either end. The file is read each time so edits
+    // show up without restarting.
+    pub fn cycle_yield_curve(&mut self, forward: bool) {
+        let mut options = vec![YieldCurve::Classic, YieldCurve::LINEAR, YieldCurve::EXPONENTIAL];
+        match load_custom_yield_curve() {
+            Ok(Some(custom)) if !options.contains(&custom) => options.push(custom),
+            Ok(_) => {},
+            Err(e) => eprintln!("Ignoring {}: {}", YIELD_CURVE_FILE, e),
+        }
+
+        let count = options.len();
+        let next = match options.iter().position(|&curve| curve == self.yield_curve) {
+            Some(index) if forward => (index + 1) % count,
+            Some(index) => (index + count - 1) % count,
+            None => 0, // a custom curve whose file has since changed
+        };
+
+        self.yield_curve = options[next];
+    }
+
+    // Income with no upgrades and with both upgrades at this game's cap, for the menu's preview
+    pub fn yield_range(&self) -> (f32, f32) {
+        let cap = self.upgrade_cap();
+        (self.yield_curve.income_per_second(0, 0), self.yield_curve.income_per_second(cap, cap))
+    }
+
+    // How many last places in a row knock a miner out from full health, for the menu's preview
+    pub fn last_places_to_elimination(&self) -> i32 {
+        let miner_count = self.bot_count + 1;
+        let last_place_damage = self.damage_model.damage_for(miner_count - 1, miner_count).max(1);
+        (self.starting_health + last_place_damage - 1) / last_place_damage
+    }
+
+    // Changing difficulty resets the first round shield to that difficulty's default (on for Easy)
+    pub fn set_difficulty(&mut self, difficulty: Difficulty) {
+        self.difficulty = difficulty;
+        self.first_round_shield = difficulty == Difficulty::Easy;
+    }
+
+    pub fn change_bot_count(&mut self, delta: i32) {
+        let count = self.bot_count as i32 + delta;
+        self.bot_count = count.clamp(MIN_BOTS as i32, MAX_BOTS as i32) as usize;
+    }
+}
+
+// Reads YIELD_CURVE_FILE: shape (linear or exponential), gold_base, gold_growth, speed_base
+// and speed_growth. Keys left out keep the Linear preset's values. None if there's no file,
+// and an error naming the first bad line or value otherwise.
+fn load_custom_yield_curve() -> Result<Option<YieldCurve>, String> {
+    let Ok(contents) = fs::read_to_string(YIELD_CURVE_FILE) else {
+        return Ok(None);
+    };
+
+    let mut params = CurveParams::LINEAR;
+
+    // `#` starts a comment, to the end of the line
+    let lines = contents.lines().map(|line| line.split('#').next().unwrap_or("").trim());
+    for line in lines.filter(|line| !line.is_empty()) {
+        let (key, value) = line.split_once('=').ok_or_else(|| format!("expected key=value, got \"{}\"", line))?;
+        let (key, value) = (key.trim(), value.trim());
+        let number = || value.parse::<f32>().map_err(|_| format!("{} should be a number, got \"{}\"", key, value));
+
+        match key {
+            "shape" => params.shape = CurveShape::from_name(value)
+                .ok_or_else(|| format!("shape should be linear or exponential, got \"{}\"", value))?,
+            "gold_base" => params.gold_base = number()?,
+            "gold_growth" => params.gold_growth = number()?,
+            "speed_base" => params.speed_base = number()?,
+            "speed_growth" => params.speed_growth = number()?,
+            _ => return Err(format!("unknown key \"{}\"", key)),
+        }
+    }
+
+    params.validate()?;
+    Ok(Some(YieldCurve::Curve(params)))
+}
@@ -1,79 +1,489 @@
-use ggez::graphics::Rect;
-use ggez::{Context, GameResult};
-use ggez::event::{EventHandler, KeyCode, KeyMods};
-use ggez::input::mouse::MouseButton;
-use rand::Rng;
+use ggez::graphics::{self, Rect};
+use ggez::{timer, Context, GameResult};
+use ggez::event::{self, EventHandler, KeyCode, KeyMods};
+use ggez::input::mouse::{self, CursorIcon, MouseButton};
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha12Rng;
 use std::time::{Duration, Instant};
 
-use crate::miner::{Miner, MinerType};
-use crate::pet::Pet;
+use crate::activity::{ActivityKind, ActivityLog};
+use crate::audio::{Audio, Sound};
+use crate::chatter::{self, ChatterEvent};
+use crate::config::GameConfig;
+use crate::miner::{BotStrategy, Miner, MinerType, UpgradeKind, PLAYER_COLOR};
+use crate::pet::{Pet, PetColor, MAX_PETS, PET_REVIVE_COST};
+use crate::save::{self, SaveData};
+use crate::settings::{LayoutMode, Settings, VolumeChannel};
+use crate::stats::LifetimeStats;
 use crate::ui;
 
 
 
 // Game constants
-pub const MAX_ROUNDS: usize = 10; // shortened to 10 from 15
+pub const MAX_ROUNDS: usize = 10; // shortened to 10 from 15; default for GameConfig::round_count
 pub const ROUND_DURATION: Duration = Duration::from_secs(30); // 30 seconds from 60 seconds for speed tuning
 pub const WINDOW_WIDTH: f32 = 800.0;
 pub const WINDOW_HEIGHT: f32 = 600.0;
 
+// Layout presets. The gameplay panels span GAMEPLAY_WIDTH (including the pet panel);
+// the compact preset shrinks that to the smallest window's width and the spacious
+// preset scales everything up by SPACIOUS_SCALE.
+const GAMEPLAY_WIDTH: f32 = 1060.0;
+const COMPACT_SCALE: f32 = WINDOW_WIDTH / GAMEPLAY_WIDTH;
+const SPACIOUS_SCALE: f32 = 1.5;
+
+// Debug options (read from the environment at startup)
+pub const CHEATS_ENV: &str = "MINEFEST_CHEATS"; // set to enable cheats in release builds
+pub const START_ROUND_ENV: &str = "MINEFEST_START_ROUND"; // jump straight to a later round
+const REWIND_WINDOW: Duration = Duration::from_secs(15); // final stretch of the round the rewind cheat replays
+
+// Tooltips
+pub const TOOLTIP_DELAY: Duration = Duration::from_millis(500); // hover time before a tooltip shows
+const TOOLTIP_GRACE: Duration = Duration::from_millis(300); // keeps tooltips up while crossing gaps between elements
+
+// Results screen
+pub const PHOTO_FINISH_MARGIN: f32 = 20.0; // donations this close count as a "photo finish"
+
+// Fixed donation buttons, also on number keys 1-5 (0 donates everything)
+pub const DONATION_TIERS: [f32; 5] = [10.0, 50.0, 100.0, 500.0, 1000.0];
+
+// How long a blocked upgrade button flashes
+pub const UPGRADE_FEEDBACK_DURATION: Duration = Duration::from_millis(1200);
+
+// How long a bot's level indicator stays highlighted after it upgrades
+pub const BOT_UPGRADE_FLASH_DURATION: Duration = Duration::from_millis(1500);
+
+// How long the sparkle around the pet icon lasts after it finds loot
+pub const PET_LOOT_SPARKLE_DURATION: Duration = Duration::from_millis(1500);
+
+// How quickly health bars catch up with the real health (fraction of the gap closed per second)
+const HEALTH_EASE_RATE: f32 = 6.0;
+
+// Usurper bots: the most of their gold (on hand plus already donated) they'll put in
+// per round, and how far past the leader they aim to get
+const USURPER_MAX_SPEND: f32 = 0.6;
+const USURPER_MARGIN: f32 = 5.0;
+
+// Survivor bots aim this far above the lowest donation, and go all in below critical health
+const SURVIVOR_MARGIN: f32 = 5.0;
+
+// A bot that lost at least this many of its remembered rounds (ROUND_MEMORY) is on a
+// losing streak: it stops upgrading mid-round to save up, and donates this much more
+const LOSING_STREAK_PIVOT: usize = 2;
+const LOSING_STREAK_DONATION_BOOST: f32 = 1.3;
+
+// Share of its gold a Random bot donates at the end of a round, rolled each time
+const RANDOM_DONATION_SHARE: std::ops::Range<f32> = 0.1..0.4;
+
+// Nemesis: share of its round's gold (on hand plus donated) it will spend beating the
+// player, growing from the first round to the last, and how far over the player it aims
+const NEMESIS_SPEND: std::ops::Range<f32> = 0.35..0.75;
+const NEMESIS_MARGIN: f32 = 5.0;
+
+// Donate All needs a second press within this long to go through
+pub const DONATE_ALL_CONFIRM_WINDOW: Duration = Duration::from_secs(3);
+
+// Reset Stats in settings needs a second press within this long
+pub const RESET_STATS_CONFIRM_WINDOW: Duration = Duration::from_secs(3);
+
+// Quit to Menu in settings needs a second press within this long
+pub const QUIT_TO_MENU_CONFIRM_WINDOW: Duration = Duration::from_secs(3);
+
+// How long the round timer bar takes to sweep back from full when the next round starts
+pub const TIMER_RESET_ANIMATION: Duration = Duration::from_millis(400);
+
+// The victory screen reveals its final stats one row at a time, this far apart
+pub const GAME_OVER_STAT_REVEAL: Duration = Duration::from_millis(250);
+
+// How long the field overview shows at the start of a round
+pub const ROUND_START_SNAPSHOT_DURATION: Duration = Duration::from_secs(4);
+
+// How long the personal recap shows on the results screen
+pub const ROUND_RECAP_DURATION: Duration = Duration::from_millis(2500);
+
+// How long the confirmation for a cheat key stays on screen
+pub const CHEAT_FLASH_DURATION: Duration = Duration::from_millis(1500);
+
+// The round timer shows tenths of a second once less than this is left
+pub const TIMER_TENTHS_BELOW: Duration = Duration::from_secs(10);
+
+// The round counter turns orange with this many rounds left after the current one,
+// and red on the final round
+pub const ROUNDS_LEFT_WARNING: usize = 2;
+
+// One full pulse of the low-health vignette
+pub const LOW_HEALTH_PULSE: Duration = Duration::from_millis(1200);
+
+// Round end choice: trade this much unspent gold for one health instead of carrying it all over
+pub const CASH_IN_COST: f32 = 500.0;
+
+// An upgrade bought with this little of the round left can be undone on the results screen
+pub const UPGRADE_UNDO_WINDOW: Duration = Duration::from_secs(3);
+
+// Game logic steps per second, independent of the frame rate, and the most one frame will catch up on
+const LOGIC_TICKS_PER_SECOND: u32 = 60;
+const LOGIC_STEP: Duration = Duration::from_nanos(1_000_000_000 / LOGIC_TICKS_PER_SECOND as u64);
+const MAX_TICKS_PER_FRAME: u32 = 10;
+
+// Bot chatter
+const CHATTER_COOLDOWN: Duration = Duration::from_secs(5); // minimum time between any two chatter lines
+const CHATTER_CHANCE: f32 = 0.5; // chance a bot speaks up when it acts (outside the cooldown)
+
 pub enum GameState {
+    Menu,
     Playing,
     RoundEnd,
     GameOver,
 }
 
+// Modal overlays drawn over the current screen. Only the topmost one gets clicks;
+// the screen underneath gets none until every overlay is dismissed.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Overlay {
+    Settings,
+    Comparison, // display only, so clicks are just swallowed
+    BotDetail, // the bot in `selected_bot`; a click outside the popup closes it
+}
+
+// Column the opponents panel is sorted by (highest first)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum OpponentSort {
+    Health,
+    Gold,
+    Donated,
+}
+
+impl OpponentSort {
+    pub const ALL: [OpponentSort; 3] = [OpponentSort::Health, OpponentSort::Gold, OpponentSort::Donated];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            OpponentSort::Health => "Health",
+            OpponentSort::Gold => "Gold",
+            OpponentSort::Donated => "Donated",
+        }
+    }
+}
+
+// One miner's place in the standings, best first. miner_index 0 is the player, i + 1 is bots[i].
+pub struct Standing {
+    pub miner_index: usize,
+    pub donated: f32,
+    pub damage: i32,
+}
+
+// How a miner's damage for the round was worked out, for the results screen tooltip
+pub struct DamageBreakdown {
+    pub placement: i32, // 1 per place below first
+    pub model: i32, // after the damage model picks who is hit
+    pub waived: Option<&'static str>, // why nobody took damage this round, if nobody did
+    pub pet_absorbed: bool,
+    pub taken: i32,
+}
+
+// Everyone's state as the round's final stretch began, for the rewind cheat
+pub struct RewindPoint {
+    pub player: Miner,
+    pub bots: Vec<Miner>,
+    pub total_gold_earned: f32,
+    pub elapsed: Duration, // round time when it was taken
+}
+
+// A live miner's gold and health as a round began, for the round start panel
+pub struct MinerSnapshot {
+    pub miner_index: usize,
+    pub gold: f32,
+    pub health: i32,
+}
+
+// Why the last upgrade click didn't go through, shown briefly on the button
+pub struct UpgradeFeedback {
+    pub kind: UpgradeKind,
+    pub message: &'static str,
+    pub time: Instant,
+}
+
+// The player's most recent upgrade click, for the results screen's undo
+pub struct LastUpgrade {
+    pub kind: UpgradeKind,
+    pub level: usize, // level it bought
+    pub round: usize,
+    pub time_left: Duration, // round time left when it was bought
+}
+
 pub struct MainState {
     pub player: Miner,
     pub bots: Vec<Miner>,
     pub current_round: usize,
     pub round_start_time: Instant,
+    pub round_paused_duration: Duration, // total time the round clock has been paused this round
+    pub round_paused_at: Option<Instant>, // set while the round clock is paused
+    pub timer_bar_reset_at: Option<Instant>, // when the round after a results screen began, for the timer bar's sweep back
     pub game_state: GameState,
     pub round_results: Option<Vec<(usize, f32)>>, // (miner_index, donated_gold)
+    pub round_damage: Vec<DamageBreakdown>, // per place in round_results
     pub past_results: Vec<bool>, // true for win, false for loss
     pub total_gold_earned: f32, // New field to track total gold earned
-    pub pet: Pet,
+    pub pets: Vec<Pet>, // owned pets, oldest first; hits go to the oldest live one
     pub show_cursor_position: bool, 
     pub cursor_position: (f32, f32), //  to store current cursor position
+    pub cheats_enabled: bool,
+    pub cheats_used: bool, // a cheat key was used this game, so it isn't recorded in lifetime stats
+    pub rewind_point: Option<RewindPoint>, // taken as the current round's final stretch began
+    pub start_round: usize, // round the game starts on (1 unless overridden for testing)
+    pub hover_tooltip: Option<String>, // tooltip text for the element under the cursor
+    pub hover_start: Instant, // when the cursor started hovering the current element
+    pub tooltip_warm_until: Option<Instant>, // until then, new tooltips show without the delay
+    pub settings: Settings,
+    pub audio: Audio,
+    pub overlays: Vec<Overlay>, // open overlays, topmost last
+    pub selected_bot: Option<usize>, // index into `bots` shown in the bot detail popup
+    pub dragging_slider: Option<VolumeChannel>, // volume slider currently being dragged
+    pub seed: u64, // seed for all game randomness, so a game can be replayed
+    pub seed_input: String, // seed typed on the menu for the next game; empty means random
+    pub seed_input_focused: bool,
+    pub rng: ChaCha12Rng, // seeded from `seed`; a ChaCha stream so its position can be saved
+    pub activity_log: ActivityLog,
+    pub last_chatter_time: Option<Instant>,
+    pub config: GameConfig, // options picked on the menu, kept across restarts
+    menu_config: Option<GameConfig>, // the menu's options while a quick match plays on the defaults
+    pub upgrade_feedback: Option<UpgradeFeedback>,
+    pub screen_size: (f32, f32), // drawable size in layout units (window size / ui_scale)
+    pub window_size: (f32, f32), // drawable size in pixels, kept in sync by resize_event
+    pub ui_scale: f32, // pixels per layout unit for the active layout preset
+    pub save_available: bool, // an auto-save exists that the menu can continue from
+    pub opponent_sort: Option<OpponentSort>, // None keeps the line-up order
+    pub show_dead_bots: bool,
+    pub show_standings_preview: bool, // S overlay projecting the round's result from live donations
+    pub show_donation_share: bool, // P toggles donations between gold and % of the round's total
+    pub lifetime_stats: LifetimeStats,
+    pub recent_bot_upgrades: Vec<(usize, UpgradeKind, Instant)>, // (bot index, upgrade, when), for the opponents panel highlight
+    pub displayed_health: Vec<f32>, // what the health bars show, by miner index (0 = player, i + 1 = bots[i])
+    pub elimination_order: Vec<usize>, // miner indices (0 = player, i + 1 = bots[i]) in the order they died
+    pub first_blood_round: Option<usize>, // round of the game's first death
+    pub time_played: Duration, // round time actually played this game (pauses and results screens don't count)
+    pub max_possible_damage: i32, // worst damage each round dealt anyone, summed over the rounds the player was alive for
+    pub gold_earned_at_round_start: f32, // total_gold_earned when the round began, for the recap
+    pub round_recap: Option<(String, Instant)>, // the player's result for the round just ended, and when it was made
+    pub cheat_flash: Option<(String, Instant)>, // what the last cheat key did, and when
+    pub round_start_snapshot: Option<(Vec<MinerSnapshot>, Instant)>, // the field as the current round began, and when
+    pub game_over_at: Option<Instant>, // when the last game ended, for the victory screen's reveal
+    pub last_round_ranking: Vec<usize>, // miner indices in finishing order, most recent round
+    pub previous_round_ranking: Vec<usize>, // the round before that, for the rank changes on the results screen
+    pub donate_all_armed_at: Option<Instant>, // first Donate All press, waiting for the confirming one
+    pub reset_stats_armed_at: Option<Instant>, // first Reset Stats press, waiting for the confirming one
+    pub quit_to_menu_armed_at: Option<Instant>, // first Quit to Menu press, waiting for the confirming one
+    pub cash_in_selected: bool, // round end choice; false keeps all the gold for next round
+    pub last_upgrade: Option<LastUpgrade>, // cleared once undone, so the undo is one-time
+    pub menu_focus: usize, // keyboard-selected menu item, see menu_item_count
+    pub settings_focus: usize, // keyboard-selected settings item, see settings_item_count
+    pub keyboard_focus_visible: bool, // focus highlight shows after arrow keys, hides again on a click
+    pub draw_error: Option<String>, // last failed frame's error; shows the error screen until dismissed
+    pub launch_vsync: bool, // the window was created with vsync; changing that needs a restart
+    pub last_frame_at: Instant, // when the last frame was presented, for the frame cap
+    pub pointer_cursor: bool, // the hand cursor is showing because the mouse is over something clickable
+    pub headless: bool, // no window and nothing written to disk (tests, balance reports)
 }
 
 impl MainState {
-    pub fn new(_ctx: &mut Context) -> GameResult<MainState> {
-        let player = Miner::new(MinerType::Player);
-        let mut bots = Vec::new();
-        
-        // Create 3 bot miners
-        for _ in 0..3 {
-            bots.push(Miner::new(MinerType::Bot));
+    // `config` is the starting menu selection (defaults, or whatever was passed on the command line)
+    pub fn new(ctx: &mut Context, config: GameConfig) -> GameResult<MainState> {
+        let settings = Settings::load();
+        let audio = Audio::new(ctx, &settings);
+        let mut state = MainState::build(config, settings, audio, rand::random());
+
+        state.cheats_enabled = cfg!(debug_assertions) || std::env::var(CHEATS_ENV).is_ok();
+
+        // Only honor the starting round override when cheats are on
+        if state.cheats_enabled {
+            state.start_round = std::env::var(START_ROUND_ENV)
+                .ok()
+                .and_then(|value| value.trim().parse::<usize>().ok())
+                .map_or(1, |round| round.clamp(1, config.round_count));
         }
-    
-        Ok(MainState {
+
+        state.screen_size = graphics::drawable_size(ctx);
+        state.window_size = graphics::drawable_size(ctx);
+        state.save_available = save::save_exists();
+        state.lifetime_stats = LifetimeStats::load();
+
+        if state.config.free_pet {
+            state.pets.push(Pet { color: state.settings.pet_color, unlocked: true, ..Pet::new() });
+        }
+        state.apply_start_round();
+        state.begin_round();
+        Ok(state)
+    }
+
+    // A game with no window, sound or files, already playing its first round with `seed`
+    pub fn headless(config: GameConfig, seed: u64) -> MainState {
+        let mut state = MainState::build(config, Settings::default(), Audio::silent(), seed);
+        state.headless = true;
+        state.seed_input = seed.to_string();
+        state.restart_game();
+        state
+    }
+
+    // Everything that doesn't need the window or the disk: the menu, at default sizes
+    fn build(config: GameConfig, settings: Settings, audio: Audio, seed: u64) -> MainState {
+        let player = spawn_player(&config);
+        let bots = spawn_bots(&config);
+        let launch_vsync = settings.frame_cap.vsync();
+
+        MainState {
             player,
             bots,
             current_round: 1,
             round_start_time: Instant::now(),
-            game_state: GameState::Playing,
+            round_paused_duration: Duration::ZERO,
+            round_paused_at: None,
+            timer_bar_reset_at: None,
+            game_state: GameState::Menu,
             round_results: None,
+            round_damage: Vec::new(),
             past_results: Vec::new(),
             total_gold_earned: 0.0,
-            pet: Pet::new(),
+            pets: Vec::new(),
             show_cursor_position: false,
             cursor_position: (0.0, 0.0),
-        })
+            cheats_enabled: false,
+            cheats_used: false,
+            rewind_point: None,
+            start_round: 1,
+            hover_tooltip: None,
+            hover_start: Instant::now(),
+            tooltip_warm_until: None,
+            settings,
+            audio,
+            overlays: Vec::new(),
+            selected_bot: None,
+            dragging_slider: None,
+            seed,
+            seed_input: String::new(),
+            seed_input_focused: false,
+            rng: ChaCha12Rng::seed_from_u64(seed),
+            activity_log: ActivityLog::new(),
+            last_chatter_time: None,
+            config,
+            menu_config: None,
+            upgrade_feedback: None,
+            screen_size: (WINDOW_WIDTH, WINDOW_HEIGHT),
+            window_size: (WINDOW_WIDTH, WINDOW_HEIGHT),
+            ui_scale: 1.0,
+            save_available: false,
+            opponent_sort: None,
+            show_dead_bots: false,
+            show_standings_preview: false,
+            show_donation_share: false,
+            lifetime_stats: LifetimeStats::default(),
+            recent_bot_upgrades: Vec::new(),
+            displayed_health: Vec::new(),
+            elimination_order: Vec::new(),
+            first_blood_round: None,
+            time_played: Duration::ZERO,
+            max_possible_damage: 0,
+            gold_earned_at_round_start: 0.0,
+            round_recap: None,
+            cheat_flash: None,
+            round_start_snapshot: None,
+            game_over_at: None,
+            last_round_ranking: Vec::new(),
+            previous_round_ranking: Vec::new(),
+            donate_all_armed_at: None,
+            reset_stats_armed_at: None,
+            quit_to_menu_armed_at: None,
+            cash_in_selected: false,
+            last_upgrade: None,
+            menu_focus: MENU_QUICK_MATCH,
+            settings_focus: 0,
+            keyboard_focus_visible: false,
+            draw_error: None,
+            launch_vsync,
+            last_frame_at: Instant::now(),
+            pointer_cursor: false,
+            headless: false,
+        }
+    }
+
+    // Fast-forwards a fresh game to `start_round`, giving the bots the upgrades
+    // and savings they would roughly have by then
+    fn apply_start_round(&mut self) {
+        if self.start_round <= 1 {
+            return;
+        }
+
+        let rounds_skipped = self.start_round - 1;
+        self.current_round = self.start_round;
+
+        for bot in &mut self.bots {
+            // Roughly one upgrade every other round, capped at max level
+            *bot.level_mut(UpgradeKind::Pickaxe) = (rounds_skipped / 2).min(bot.max_level);
+            *bot.level_mut(UpgradeKind::Mine) = rounds_skipped.div_ceil(2).min(bot.max_level);
+            bot.gold = rounds_skipped as f32 * 250.0;
+        }
     }
     
 
     pub fn bot_make_decision(&mut self, bot_index: usize) {
-        let bot = &mut self.bots[bot_index];
-        if !bot.alive {
+        if !self.bots[bot_index].alive {
+            return;
+        }
+
+        // Practice mode: bots only ever work on their economy
+        if self.config.sandbox {
+            self.bot_consider_upgrades(bot_index);
+            return;
+        }
+
+        // Once donations are locked in, bots can't react to late donations any more
+        if self.donations_locked() {
+            self.bot_consider_upgrades(bot_index);
+            return;
+        }
+
+        // Turn-based mode: a bot gets one move per turn, so it sees to its economy first
+        // (unless it's saving up) and then plays the end of the round straight away
+        if self.config.turn_based {
+            self.bot_upgrade_unless_saving(bot_index);
+        }
+
+        // Calculate time left in the round to determine "end of round" behavior.
+        // With a donation lock the bots treat the lock as the end of the round.
+        let round_elapsed = self.round_elapsed();
+        let donation_window = self.config.round_duration.saturating_sub(self.config.donation_lock);
+        let round_progress = round_elapsed.as_secs_f32() / donation_window.as_secs_f32();
+        let is_end_of_round = self.config.turn_based || round_progress >= 0.8; // Last 20% of the round
+
+        // The Random strategy rolls its share of gold when it comes to donate
+        let bot = &self.bots[bot_index];
+        let rolls_share = bot.strategy.unwrap_or(BotStrategy::Random) == BotStrategy::Random
+            && is_end_of_round
+            && !bot.has_donated_this_round;
+        let random_share = if rolls_share { self.rng.gen_range(RANDOM_DONATION_SHARE) } else { 0.0 };
+        let end_of_round_donation = self.bot_end_of_round_donation(bot_index, random_share);
+
+        // Usurpers and the nemesis donate as often as it takes to stay ahead, so they skip the
+        // once-per-round check below
+        if matches!(self.bots[bot_index].strategy, Some(BotStrategy::Usurper) | Some(BotStrategy::Nemesis)) {
+            if is_end_of_round {
+                self.bots[bot_index].contribute_gold(end_of_round_donation);
+            } else {
+                self.bot_upgrade_unless_saving(bot_index);
+            }
+            return;
+        }
+
+        // Survivors top up whenever someone passes them, so they skip it too
+        if self.bots[bot_index].strategy == Some(BotStrategy::Survivor) {
+            if is_end_of_round {
+                self.bots[bot_index].contribute_gold(end_of_round_donation);
+            } else {
+                self.bot_upgrade_unless_saving(bot_index);
+            }
             return;
         }
 
-        // Calculate time left in the round to determine "end of round" behavior
-        let now = std::time::Instant::now();
-        let round_elapsed = now.duration_since(self.round_start_time);
-        let round_progress = round_elapsed.as_secs_f32() / ROUND_DURATION.as_secs_f32();
-        let is_end_of_round = round_progress >= 0.8; // Last 20% of the round
+        let bot = &mut self.bots[bot_index];
         
         // Skip donation logic if bot has already donated this round
         if bot.has_donated_this_round {
@@ -82,498 +492,2678 @@ impl MainState {
             return;
         }
         
-        // Get upgrade costs
-        let pickaxe_cost = bot.pickaxe_upgrade_cost();
-        //let mine_cost = bot.mine_upgrade_cost();
+        // The early-round strategies below open with a pickaxe before anything else
+        let has_no_upgrades = UpgradeKind::ALL.iter().all(|&kind| bot.level(kind) == 0);
         
-        // Different strategies based on bot index
-        match bot_index {
-            0 => {
-                // Bot 1: Economy-focused bot
+        // Different strategies based on the bot's personality
+        match bot.strategy.unwrap_or(BotStrategy::Random) {
+            BotStrategy::Economy => {
+                // Economy-focused bot
 
-                // If less than 3 hp, donate all gold
-                if bot.health < 3 {
+                // Below critical health, donate all gold
+                if bot.health < bot.critical_health() {
                     let contribution = bot.gold;
                     if contribution > 0.0 {
                         bot.contribute_gold(contribution);
                     }
                 }
                 
-                // Only consider donating at end of round
+                // Only consider donating at end of round (10% of gold, once the above is spent)
                 if is_end_of_round {
-                    // Donate 10% of gold at end of round
-                    let contribution = bot.gold * 0.1;
+                    let contribution = bot.gold.min(end_of_round_donation);
                     if contribution > 0.0 {
                         bot.contribute_gold(contribution);
                     }
                 } else {
                     // Not end of round, focus on upgrades
-                    self.bot_consider_upgrades(bot_index);
+                    self.bot_upgrade_unless_saving(bot_index);
                 }
             },
-            1 => {
-                // Bot 2: Aggressive end-round donator
+            BotStrategy::Aggressive => {
+                // Aggressive end-round donator
                 
                 // In early rounds, focus on getting at least one upgrade
-                if self.current_round <= 2 && bot.pickaxe_level == 0 && bot.mine_level == 0 {
-                    if bot.gold >= pickaxe_cost {
-                        bot.upgrade_pickaxe();
-                        return;
-                    }
+                if self.current_round <= 2 && has_no_upgrades && bot.upgrade(UpgradeKind::Pickaxe) {
+                    return;
                 }
                 
                 // End of round donation with health-based amounts
                 if is_end_of_round {
-                    let contribution_percentage = if bot.health < 3 {
-                        0.9 // 90% when critically low HP
-                    } else if bot.health < 5 {
-                        0.5 // 50% when low HP
-                    } else {
-                        0.7 // 70% normally
-                    };
-                    
-                    let contribution = bot.gold * contribution_percentage;
+                    let contribution = end_of_round_donation;
                     if contribution > 0.0 {
                         bot.contribute_gold(contribution);
                     }
                 } else {
                     // Not end of round, focus on upgrades
-                    self.bot_consider_upgrades(bot_index);
+                    self.bot_upgrade_unless_saving(bot_index);
                 }
             },
-            2 => {
-                // Bot 3: Mixed/balanced playstyle
+            BotStrategy::Balanced => {
+                // Mixed/balanced playstyle
                 
                 // In very early rounds, try to get at least one upgrade first
-                if self.current_round == 1 && bot.pickaxe_level == 0 && bot.mine_level == 0 {
-                    if bot.gold >= pickaxe_cost {
-                        bot.upgrade_pickaxe();
-                        return;
-                    }
+                if self.current_round == 1 && has_no_upgrades && bot.upgrade(UpgradeKind::Pickaxe) {
+                    return;
                 }
                 
                 // End of round donation with health-based amounts
                 if is_end_of_round {
-                    let contribution_percentage = if bot.health < 3 {
-                        0.9 // 90% when critically low HP
-                    } else {
-                        0.3 // 30% normally
-                    };
-                    
-                    let contribution = bot.gold * contribution_percentage;
+                    let contribution = end_of_round_donation;
                     if contribution > 0.0 {
                         bot.contribute_gold(contribution);
                     }
                 } else {
                     // Not end of round, focus on upgrades
-                    self.bot_consider_upgrades(bot_index);
+                    self.bot_upgrade_unless_saving(bot_index);
                 }
             },
-            _ => {
+            BotStrategy::Usurper | BotStrategy::Survivor | BotStrategy::Nemesis => {
+                // Handled by the early returns above
+            },
+            BotStrategy::Random => {
                 // Fallback behavior
                 // Only donate at end of round
                 if is_end_of_round && !bot.has_donated_this_round {
-                    let mut rng = rand::thread_rng();
-                    let contribution_percentage = rng.gen_range(0.1..0.4);
-                    let contribution = bot.gold * contribution_percentage;
+                    let contribution = end_of_round_donation;
                     if contribution > 0.0 {
                         bot.contribute_gold(contribution);
                     }
                 } else {
                     // Not end of round, focus on upgrades
-                    self.bot_consider_upgrades(bot_index);
+                    self.bot_upgrade_unless_saving(bot_index);
                 }
             }
         }
     }
 
-    fn bot_consider_upgrades(&mut self, bot_index: usize) {
-        let bot = &mut self.bots[bot_index];
-        
-        // Skip if bot is dead
-        if !bot.alive {
-            return;
-        }
-        
-        let pickaxe_cost = bot.pickaxe_upgrade_cost();
-        let mine_cost = bot.mine_upgrade_cost();
-        
-        match bot_index {
-            0 => {
-                // Bot 1: Focus on upgrading the lowest level
-                if bot.pickaxe_level < bot.mine_level && 
-                   bot.pickaxe_level < 4 && 
-                   bot.gold >= pickaxe_cost {
-                    // Upgrade pickaxe since it's lower
-                    bot.upgrade_pickaxe();
-                } else if bot.mine_level < bot.pickaxe_level && 
-                          bot.mine_level < 4 && 
-                          bot.gold >= mine_cost {
-                    // Upgrade mine since it's lower
-                    bot.upgrade_mine();
-                } else if bot.pickaxe_level < 4 && bot.gold >= pickaxe_cost {
-                    // If levels are equal, upgrade pickaxe
-                    bot.upgrade_pickaxe();
-                } else if bot.mine_level < 4 && bot.gold >= mine_cost {
-                    // If pickaxe is maxed, upgrade mine
-                    bot.upgrade_mine();
-                }
+    // Gold the bot's end-of-round branch of bot_make_decision would donate right now, worked
+    // out without giving it, so the opponents panel forecast and the real decision agree.
+    // `random_share` stands in for the Random strategy's roll.
+    fn bot_end_of_round_donation(&self, bot_index: usize, random_share: f32) -> f32 {
+        let bot = &self.bots[bot_index];
+
+        // Difficulty (and the adaptive adjustment, if on) scales every percentage-based donation,
+        // as does a losing streak
+        let streak_boost = if self.bot_on_losing_streak(bot_index) { LOSING_STREAK_DONATION_BOOST } else { 1.0 };
+        let donation_factor = self.config.difficulty.bot_donation_factor() * self.adaptive_factor() * streak_boost;
+        let scaled = |percentage: f32| (percentage * donation_factor).min(1.0);
+
+        match bot.strategy.unwrap_or(BotStrategy::Random) {
+            BotStrategy::Usurper => self.usurper_chase_amount(bot_index, donation_factor),
+            BotStrategy::Survivor => self.survivor_escape_amount(bot_index),
+            BotStrategy::Nemesis => self.nemesis_counter_amount(bot_index),
+            // Everyone else donates once per round
+            _ if bot.has_donated_this_round => 0.0,
+            // Economy bots go all in when critically low
+            BotStrategy::Economy if bot.health < bot.critical_health() => bot.gold,
+            BotStrategy::Economy => bot.gold * scaled(0.1),
+            BotStrategy::Aggressive => {
+                let contribution_percentage = if bot.health < bot.critical_health() {
+                    0.9 // 90% when critically low HP
+                } else if bot.health < bot.low_health() {
+                    0.5 // 50% when low HP
+                } else {
+                    0.7 // 70% normally
+                };
+                bot.gold * scaled(contribution_percentage)
             },
-            1 => {
-                // Bot 2: Random upgrades with fallback
-                let mut rng = rand::thread_rng();
-                let upgrade_decision = rng.gen_range(0..2); // 0: Pickaxe, 1: Mine
-                
-                match upgrade_decision {
-                    0 => {
-                        if bot.pickaxe_level < 4 && bot.gold >= bot.pickaxe_upgrade_cost() {
-                            bot.upgrade_pickaxe();
-                        } else if bot.mine_level < 4 && bot.gold >= bot.mine_upgrade_cost() {
-                            // Try mine upgrade as fallback
-                            bot.upgrade_mine();
-                        }
-                    },
-                    1 => {
-                        if bot.mine_level < 4 && bot.gold >= bot.mine_upgrade_cost() {
-                            bot.upgrade_mine();
-                        } else if bot.pickaxe_level < 4 && bot.gold >= bot.pickaxe_upgrade_cost() {
-                            // Try pickaxe upgrade as fallback
-                            bot.upgrade_pickaxe();
-                        }
-                    },
-                    _ => {}
-                }
-            },
-            2 => {
-                // Bot 3: Balanced upgrades
-                if bot.pickaxe_level < bot.mine_level && 
-                   bot.pickaxe_level < 4 && 
-                   bot.gold >= pickaxe_cost {
-                    // Prioritize pickaxe to catch up
-                    bot.upgrade_pickaxe();
-                } else if bot.mine_level < bot.pickaxe_level && 
-                          bot.mine_level < 4 && 
-                          bot.gold >= mine_cost {
-                    // Prioritize mine to catch up
-                    bot.upgrade_mine();
+            BotStrategy::Balanced => {
+                let contribution_percentage = if bot.health < bot.critical_health() {
+                    0.9 // 90% when critically low HP
                 } else {
-                    // If levels are equal, decide randomly which to upgrade
-                    let mut rng = rand::thread_rng();
-                    let upgrade_choice = rng.gen_range(0..2);
-                    
-                    if upgrade_choice == 0 && 
-                       bot.pickaxe_level < 4 && 
-                       bot.gold >= pickaxe_cost {
-                        bot.upgrade_pickaxe();
-                    } else if upgrade_choice == 1 && 
-                              bot.mine_level < 4 && 
-                              bot.gold >= mine_cost {
-                        bot.upgrade_mine();
-                    }
-                }
+                    0.3 // 30% normally
+                };
+                bot.gold * scaled(contribution_percentage)
             },
-            _ => {
-                // Fallback random behavior
-                let mut rng = rand::thread_rng();
-                let decision = rng.gen_range(0..2); // 0: Upgrade pickaxe, 1: Upgrade mine
-
-                match decision {
-                    0 => {
-                        if bot.pickaxe_level < 4 && bot.gold >= bot.pickaxe_upgrade_cost() {
-                            bot.upgrade_pickaxe();
-                        }
-                    },
-                    1 => {
-                        if bot.mine_level < 4 && bot.gold >= bot.mine_upgrade_cost() {
-                            bot.upgrade_mine();
-                        }
-                    },
-                    _ => {}
-                }
-            }
+            BotStrategy::Random => bot.gold * scaled(random_share),
         }
     }
 
-    pub fn unlock_pet(&mut self) {
-        if !self.pet.unlocked && self.player.gold >= 1000.0 {
-            self.player.gold -= 1000.0;
-            self.pet.unlock();
+    // Forecast of what a bot will likely add to its donation by the end of the round, for
+    // the Bot Forecast assist. None while the bot has nothing more to give or can't donate.
+    pub fn projected_bot_donation(&self, bot_index: usize) -> Option<f32> {
+        if !self.config.bot_forecast || self.config.sandbox || self.donations_locked() || !self.bots[bot_index].alive {
+            return None;
         }
-    }
 
-    pub fn toggle_pet_mining(&mut self) {
-        self.pet.toggle_mining();
+        // The middle of the Random strategy's roll
+        let random_share = (RANDOM_DONATION_SHARE.start + RANDOM_DONATION_SHARE.end) / 2.0;
+        let amount = self.bot_end_of_round_donation(bot_index, random_share);
+        (amount > 0.0).then_some(amount)
     }
 
-    pub fn toggle_pet_searching(&mut self) {
-        self.pet.toggle_searching();
-    }
+    // Just enough to take first place from the current leader (maybe the player), or
+    // nothing if that would go over the round's spending cap - then it doesn't chase at all
+    fn usurper_chase_amount(&self, bot_index: usize, donation_factor: f32) -> f32 {
+        let leader_donation = std::iter::once(&self.player)
+            .chain(self.bots.iter())
+            .enumerate()
+            .filter(|(index, miner)| *index != bot_index + 1 && miner.alive)
+            .map(|(_, miner)| miner.donated_gold)
+            .fold(0.0, f32::max);
 
-    pub fn pet_take_hit(&mut self) {
-        self.pet.take_hit();
-    }
+        let bot = &self.bots[bot_index];
+        let needed = leader_donation + USURPER_MARGIN - bot.donated_gold;
+        if needed <= 0.0 {
+            // Already on top
+            return 0.0;
+        }
 
-    pub fn end_round(&mut self) {
-        // Collect all miners' donated gold amounts (including player)
-        let mut results = Vec::new();
-        
-        // Add player
-        results.push((0, self.player.donated_gold));
-        
-        // Add bots
-        for (i, bot) in self.bots.iter().enumerate() {
-            if bot.alive {
-                results.push((i + 1, bot.donated_gold));
-            }
+        let budget = (bot.gold + bot.donated_gold) * (USURPER_MAX_SPEND * donation_factor).min(1.0);
+        if bot.donated_gold + needed <= budget && needed <= bot.gold {
+            needed
+        } else {
+            0.0
         }
-        
-        // Sort by donated gold (highest first)
-        results.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap());
-        
-        // Record if the player won this round (was ranked #1)
-        let player_won = results.first().map_or(false, |(index, _)| *index == 0);
-        self.past_results.push(player_won);
-        
-        // Assign damage based on position
-        for (position, (miner_index, _)) in results.iter().enumerate() {
-            let damage = position as i32;
-            
-            if *miner_index == 0 {
-                // Player - check if pet can take the hit instead
-                if self.pet.unlocked && self.pet.alive && damage > 0 {
-                    self.pet.take_hit();
-                } else {
-                    self.player.take_damage(damage);
-                }
-            } else {
-                // Bot
-                self.bots[*miner_index - 1].take_damage(damage);
-            }
+    }
+
+    // Just enough to get above the lowest donation among the other live miners, keeping
+    // everything else. Low on health it stops saving and donates it all.
+    fn survivor_escape_amount(&self, bot_index: usize) -> f32 {
+        let lowest_donation = std::iter::once(&self.player)
+            .chain(self.bots.iter())
+            .enumerate()
+            .filter(|(index, miner)| *index != bot_index + 1 && miner.alive)
+            .map(|(_, miner)| miner.donated_gold)
+            .reduce(f32::min);
+
+        let bot = &self.bots[bot_index];
+
+        if bot.health < bot.critical_health() {
+            return bot.gold;
         }
-        
-        // Reset donated gold
-        self.player.donated_gold = 0.0;
-        for bot in &mut self.bots {
-            bot.donated_gold = 0.0;
+
+        // Nobody else is alive, so there is no last place to escape
+        let Some(lowest_donation) = lowest_donation else {
+            return 0.0;
+        };
+
+        // Out of reach means donating wouldn't help, so it keeps the gold
+        let needed = lowest_donation + SURVIVOR_MARGIN - bot.donated_gold;
+        if needed > 0.0 && needed <= bot.gold {
+            needed
+        } else {
+            0.0
         }
-        
-        // Store results for display
-        self.round_results = Some(results);
-        
-        // Check win/loss conditions
-        
-        // Check if player is dead
+    }
+
+    // Share of its gold the nemesis will put toward beating the player this round, more
+    // the closer the game gets to its last round
+    fn nemesis_spend_share(&self) -> f32 {
+        let progress = if self.config.round_count > 1 {
+            (self.current_round.saturating_sub(1) as f32 / (self.config.round_count - 1) as f32).min(1.0)
+        } else {
+            1.0
+        };
+        NEMESIS_SPEND.start + (NEMESIS_SPEND.end - NEMESIS_SPEND.start) * progress
+    }
+
+    // Just enough to finish above the player, ignoring every other bot. It aims for the
+    // player's usual donation over their last few rounds when that's within its spending
+    // share, so a late donation doesn't catch it out, and otherwise for what they've given
+    // so far. Nothing if neither is in reach.
+    fn nemesis_counter_amount(&self, bot_index: usize) -> f32 {
+        let bot = &self.bots[bot_index];
         if !self.player.alive {
-            self.game_state = GameState::GameOver;
-            return;
+            return 0.0;
         }
-        
-        // Check if all bots are dead
-        let bots_alive = self.bots.iter().any(|bot| bot.alive);
-        if !bots_alive {
-            self.game_state = GameState::GameOver;
+
+        let live = self.player.donated_gold;
+        let expected = self.player.round_memory.average_donation().unwrap_or(0.0).max(live);
+        let budget = (bot.gold + bot.donated_gold) * self.nemesis_spend_share();
+
+        [expected, live]
+            .into_iter()
+            .map(|target| target + NEMESIS_MARGIN - bot.donated_gold)
+            .find(|&needed| bot.donated_gold + needed <= budget && needed <= bot.gold)
+            .map_or(0.0, |needed| needed.max(0.0))
+    }
+
+    // Picks this game's nemesis from the line-up with the game's seed
+    fn designate_nemesis(&mut self) {
+        if self.bots.is_empty() {
             return;
         }
-        
-        // Check if max rounds reached
-        if self.current_round >= MAX_ROUNDS {
-            self.game_state = GameState::GameOver;
+        let index = self.rng.gen_range(0..self.bots.len());
+        self.bots[index].strategy = Some(BotStrategy::Nemesis);
+    }
+
+    // The nemesis's line at the end of a round, taunting or grumbling depending on who
+    // finished higher
+    fn nemesis_round_end_line(&mut self) {
+        let Some(bot_index) = self.bots.iter().position(|bot| bot.strategy == Some(BotStrategy::Nemesis) && bot.alive) else {
             return;
-        }
-        
-        // If we're still here, continue to the next round
-        self.game_state = GameState::RoundEnd;
+        };
+        let (Some(nemesis_place), Some(player_place)) = (
+            self.last_round_ranking.iter().position(|&index| index == bot_index + 1),
+            self.last_round_ranking.iter().position(|&index| index == 0),
+        ) else {
+            return;
+        };
+
+        let lines = if nemesis_place < player_place { chatter::NEMESIS_TAUNTS } else { chatter::NEMESIS_GRUMBLES };
+        let line = lines[self.rng.gen_range(0..lines.len())];
+        self.activity_log.push_miner(
+            bot_index + 1,
+            ActivityKind::Chatter,
+            line.replace("{}", BotStrategy::Nemesis.name()),
+            self.round_elapsed()
+        );
     }
-    
-    pub fn player_has_won(&self) -> bool {
-        // Player wins if they're alive and all bots are dead
-        self.player.alive && !self.bots.iter().any(|bot| bot.alive)
+
+    // The strategy shown for `bot` in the opponents panel and popup. The nemesis is always
+    // shown; the rivalry is the point.
+    pub fn revealed_strategy(&self, bot: &Miner) -> Option<BotStrategy> {
+        bot.strategy.filter(|&strategy| self.settings.reveal_strategies || strategy == BotStrategy::Nemesis)
     }
 
-    pub fn start_next_round(&mut self) {
-        self.current_round += 1;
-        self.round_start_time = Instant::now();
-        self.game_state = GameState::Playing;
-        self.round_results = None;
-        
-        // Reset donation flags for all miners
-        self.player.has_donated_this_round = false;
-        for bot in &mut self.bots {
-            bot.has_donated_this_round = false;
-        }
+    // Whether the bot's recent rounds have gone badly enough to change how it plays
+    fn bot_on_losing_streak(&self, bot_index: usize) -> bool {
+        self.bots[bot_index].round_memory.losses() >= LOSING_STREAK_PIVOT
     }
 
-    pub fn restart_game(&mut self) {
-        self.player = Miner::new(MinerType::Player);
-        self.bots = Vec::new();
-        for _ in 0..3 {
-            self.bots.push(Miner::new(MinerType::Bot));
+    // Mid-round upgrades, skipped while on a losing streak so the gold goes to surviving instead
+    fn bot_upgrade_unless_saving(&mut self, bot_index: usize) {
+        if !self.bot_on_losing_streak(bot_index) {
+            self.bot_consider_upgrades(bot_index);
         }
-        self.current_round = 1;
-        self.round_start_time = Instant::now();
-        self.game_state = GameState::Playing;
-        self.round_results = None;
-        self.past_results = Vec::new();
-        self.total_gold_earned = 0.0;
-        self.pet = Pet::new(); // Reset the pet
-        self.show_cursor_position = false;
-        self.cursor_position = (0.0, 0.0);
     }
 
-    pub fn handle_game_ui_click(&mut self, x: f32, y: f32) {
-        // Check pickaxe upgrade button
-        let pickaxe_btn_rect = Rect::new(30.0, 220.0, 200.0, 40.0);
-        if x >= pickaxe_btn_rect.x && x <= pickaxe_btn_rect.x + pickaxe_btn_rect.w && 
-        y >= pickaxe_btn_rect.y && y <= pickaxe_btn_rect.y + pickaxe_btn_rect.h {
-            self.player.upgrade_pickaxe();
-        }
+    fn bot_consider_upgrades(&mut self, bot_index: usize) {
+        let bot = &mut self.bots[bot_index];
         
-        // Check mine upgrade button
-        let mine_btn_rect = Rect::new(30.0, 270.0, 200.0, 40.0);
-        if x >= mine_btn_rect.x && x <= mine_btn_rect.x + mine_btn_rect.w && 
-        y >= mine_btn_rect.y && y <= mine_btn_rect.y + mine_btn_rect.h {
-            self.player.upgrade_mine();
+        // Skip if bot is dead
+        if !bot.alive {
+            return;
         }
         
-        // Check contribute buttons
-        let contribution_amounts = [10.0, 50.0, 100.0, 500.0, 1000.0];
-        let contrib_btn_x = WINDOW_WIDTH - 240.0;
-        let contrib_btn_width = 220.0;
-        
-        // Check numeric contribution options
-        for (i, amount) in contribution_amounts.iter().enumerate() {
-            let y_pos = 190.0 + (i as f32 * 40.0);
-            
-            if x >= contrib_btn_x && x <= contrib_btn_x + contrib_btn_width && 
-            y >= y_pos && y <= y_pos + 30.0 && *amount <= self.player.gold {
-                self.player.contribute_gold(*amount);
-                break;
+        match bot.strategy.unwrap_or(BotStrategy::Random) {
+            BotStrategy::Economy | BotStrategy::Usurper | BotStrategy::Survivor | BotStrategy::Nemesis => {
+                // Focus on upgrading the lowest level (ties go to the first listed),
+                // falling back to whatever else is affordable
+                let mut by_level = UpgradeKind::ALL;
+                by_level.sort_by_key(|&kind| bot.level(kind));
+
+                if let Some(kind) = by_level.into_iter().find(|&kind| bot.can_upgrade(kind, bot.gold)) {
+                    bot.upgrade(kind);
+                }
+            },
+            BotStrategy::Aggressive => {
+                // Random upgrade, falling back to the next ones in the list
+                let first = self.rng.gen_range(0..UpgradeKind::ALL.len());
+                let fallback = (0..UpgradeKind::ALL.len())
+                    .map(|offset| UpgradeKind::ALL[(first + offset) % UpgradeKind::ALL.len()])
+                    .find(|&kind| bot.can_upgrade(kind, bot.gold));
+
+                if let Some(kind) = fallback {
+                    bot.upgrade(kind);
+                }
+            },
+            BotStrategy::Balanced => {
+                balanced_upgrade(bot, &mut self.rng, 0.0);
+            },
+            BotStrategy::Random => {
+                // Fallback random behavior: one random upgrade, if it's affordable
+                let kind = UpgradeKind::ALL[self.rng.gen_range(0..UpgradeKind::ALL.len())];
+                bot.upgrade(kind);
             }
         }
-        
-        // Check "All" option
-        let all_y_pos = 190.0 + (contribution_amounts.len() as f32 * 40.0);
-        
-        if x >= contrib_btn_x && x <= contrib_btn_x + contrib_btn_width && 
-        y >= all_y_pos && y <= all_y_pos + 30.0 && self.player.gold > 0.0 {
-            self.player.contribute_gold(self.player.gold);
-        }
-        
-        // To:do - check Pet interface buttons
-        // To:do - check if pet is unlocked and alive
-        // Check for the abilities
     }
 
-    pub fn handle_round_end_ui_click(&mut self, x: f32, y: f32) {
-        if let Some(results) = &self.round_results {
-            // Calculate panel dimensions to match the UI drawing code
-            let panel_height = (results.len() as f32 * 40.0) + 150.0; // Increased panel height for button
-            let panel_y = WINDOW_HEIGHT / 2.0 - panel_height / 2.0;
-            
-            // Continue button position - exactly matching what's drawn in the UI
-            let button_rect = Rect::new(
-                WINDOW_WIDTH / 2.0 - 125.0,
-                panel_y + panel_height - 60.0,
-                250.0,
-                40.0
-            );
-            
-            if x >= button_rect.x && x <= button_rect.x + button_rect.w &&
-               y >= button_rect.y && y <= button_rect.y + button_rect.h {
-                self.start_next_round();
+    // Auto-upgrade assistant: buys the player's upgrades like a Balanced bot would,
+    // keeping the reserve from settings for donations
+    fn player_auto_upgrade(&mut self) {
+        let before = self.player;
+        balanced_upgrade(&mut self.player, &mut self.rng, self.settings.auto_upgrade_reserve);
+
+        for kind in UpgradeKind::ALL {
+            if self.player.level(kind) > before.level(kind) {
+                self.audio.queue(Sound::Upgrade);
+                self.activity_log.push_miner(
+                    0,
+                    ActivityKind::Player,
+                    format!("Auto-upgraded {} to Lv{}", kind.label().to_lowercase(), self.player.level(kind)),
+                    self.round_elapsed()
+                );
             }
         }
     }
 
-    pub fn handle_game_over_ui_click(&mut self, x: f32, y: f32) {
-        // Panel position calculation to match the UI drawing code
-        let panel_rect = Rect::new(
-            WINDOW_WIDTH / 2.0 - 250.0,
-            WINDOW_HEIGHT / 2.0 - 200.0,
-            500.0,
-            400.0
-        );
-        
-        // Check restart button - positioned to match what's drawn in the UI
-        let restart_rect = Rect::new(
-            WINDOW_WIDTH / 2.0 - 75.0,
-            panel_rect.y + 330.0,
-            150.0,
-            40.0
-        );
-        
-        if x >= restart_rect.x && x <= restart_rect.x + restart_rect.w &&
-        y >= restart_rect.y && y <= restart_rect.y + restart_rect.h {
-            self.restart_game();
+    // Runs the logic steps due this frame, at most MAX_TICKS_PER_FRAME so a long hitch can't
+    // come back as a burst, and stops early if the round ends partway
+    fn run_logic_ticks(&mut self, ticks: u32) {
+        for _ in 0..ticks.min(MAX_TICKS_PER_FRAME) {
+            if !matches!(self.game_state, GameState::Playing) {
+                break; // the round ended during an earlier step
+            }
+            self.tick_round();
         }
     }
-}
 
-impl EventHandler for MainState {
-    fn update(&mut self, ctx: &mut Context) -> GameResult {
-        match self.game_state {
-            GameState::Playing => {
-                let previous_gold = self.player.gold;
+    // One fixed step of a round in progress: mining, pets, bot turns, auto-upgrade and the round end check
+    fn tick_round(&mut self) {
+        self.ease_displayed_health(LOGIC_STEP.as_secs_f32());
 
-                // Update player and bots
-                self.player.update(ctx);
-                for bot in &mut self.bots {
-                    bot.update(ctx);
-                }
-                
-                // Update pet mining if active
-                if self.pet.unlocked && self.pet.alive && self.pet.mining {
-                    let now = Instant::now();
-                    let elapsed = now.duration_since(self.pet.last_mine_time);
-                    let pet_mine_rate = self.player.mine_rate() * 2; // Half the player's speed
-                    
-                    if elapsed >= pet_mine_rate {
-                        // Pet mines gold at half the player's rate
-                        let gold_amount = self.player.gold_per_mine() / 2.0;
+        // Remember the field as the final stretch begins, so cheat builds can replay it
+        if self.cheats_enabled && self.rewind_point.is_none() && self.round_time_left() <= REWIND_WINDOW {
+            self.rewind_point = Some(RewindPoint {
+                player: self.player,
+                bots: self.bots.clone(),
+                total_gold_earned: self.total_gold_earned,
+                elapsed: self.round_elapsed(),
+            });
+        }
+
+        let previous_gold = self.player.gold;
+
+        // Update player and bots
+        self.player.update(LOGIC_STEP);
+        for bot in &mut self.bots {
+            bot.update(LOGIC_STEP);
+        }
+        
+        // Pets mine or search for loot, each on its own timer
+        let round_time = self.round_elapsed();
+        let pet_mine_rate = self.pet_mine_rate();
+        for pet in self.pets.iter_mut().filter(|pet| pet.unlocked && pet.alive) {
+            if pet.mining {
+                if pet.advance(LOGIC_STEP, pet_mine_rate) {
+                    // Pet mines gold at half the player's rate
+                    let gold_amount = self.player.gold_per_mine() / 2.0;
+                    self.player.gold += gold_amount;
+                    self.total_gold_earned += gold_amount;
+                    pet.gold_mined += gold_amount;
+                }
+            } else if pet.searching {
+                // Check every 5 seconds for loot
+                if pet.advance(LOGIC_STEP, Duration::from_secs(5)) {
+                    // 10% chance to find loot
+                    let found_loot = self.rng.gen_range(0..10) == 0;
+                    
+                    if found_loot {
+                        // For now, just give some random gold (stub implementation)
+                        let gold_amount = self.rng.gen_range(50.0..200.0);
                         self.player.gold += gold_amount;
                         self.total_gold_earned += gold_amount;
-                        self.pet.last_mine_time = now;
+                        pet.loot_found += gold_amount;
+
+                        // Make the find noticeable: sparkle on the pet icon, a chime and a log line
+                        pet.loot_found_at = Some(Instant::now());
+                        self.audio.queue(Sound::PetLoot);
+                        self.activity_log.push_miner(
+                            0,
+                            ActivityKind::Player,
+                            format!("Your pet found {}g of loot!", self.settings.format_gold(gold_amount)),
+                            round_time
+                        );
                     }
                 }
-                
-                // Add random loot finding for pet if searching
-                if self.pet.unlocked && self.pet.alive && self.pet.searching {
-                    let now = Instant::now();
-                    let elapsed = now.duration_since(self.pet.last_mine_time);
-                    
-                    // Check every 5 seconds for loot
-                    if elapsed >= Duration::from_secs(5) {
-                        // 10% chance to find loot
-                        let mut rng = rand::thread_rng();
-                        let found_loot = rng.gen_range(0..10) == 0;
-                        
-                        if found_loot {
-                            // For now, just give some random gold (stub implementation)
-                            let gold_amount = rng.gen_range(50.0..200.0);
-                            self.player.gold += gold_amount;
-                            self.total_gold_earned += gold_amount;
-                        }
-                        
-                        self.pet.last_mine_time = now;
-                    }
+            }
+        }
+
+        let gold_earned_this_update = self.player.gold - previous_gold;
+        if gold_earned_this_update > 0.0 {
+            self.total_gold_earned += gold_earned_this_update;
+        }
+        
+        // Make random decisions for the bots still alive. Dead bots stay in the
+        // vector (end_round and the UI index into it), they just don't act.
+        let alive_bots: Vec<usize> = (0..self.bots.len())
+            .filter(|&i| self.bots[i].alive)
+            .collect();
+        for i in alive_bots {
+            self.bot_take_turn(i);
+        }
+
+        if self.settings.auto_upgrade && self.player.alive {
+            self.player_auto_upgrade();
+        }
+
+        // Check if round is over
+        if self.round_elapsed() >= self.config.round_duration {
+            self.end_round();
+        }
+    }
+
+    // Turn-based mode: with no clock to mine against, everyone starts the turn with a full
+    // round's worth of mining (pets that are mining included) at their current levels
+    fn start_turn(&mut self) {
+        if !self.config.turn_based {
+            return;
+        }
+
+        let round_length = self.config.round_duration;
+        let mut gold_earned = self.player.mine_for(round_length);
+        for bot in &mut self.bots {
+            bot.mine_for(round_length);
+        }
+
+        let pet_payouts = round_length.as_secs_f32() / self.pet_mine_rate().as_secs_f32();
+        let pet_gold = self.player.gold_per_mine() / 2.0 * pet_payouts;
+        for pet in self.pets.iter_mut().filter(|pet| pet.unlocked && pet.alive && pet.mining) {
+            self.player.gold += pet_gold;
+            pet.gold_mined += pet_gold;
+            gold_earned += pet_gold;
+        }
+        self.total_gold_earned += gold_earned;
+
+        if self.settings.auto_upgrade && self.player.alive {
+            self.player_auto_upgrade();
+        }
+    }
+
+    // Turn-based mode: the bots each make their move and the round is scored
+    pub fn end_turn(&mut self) {
+        if !self.config.turn_based || !matches!(self.game_state, GameState::Playing) {
+            return;
+        }
+
+        let alive_bots: Vec<usize> = (0..self.bots.len())
+            .filter(|&i| self.bots[i].alive)
+            .collect();
+        for i in alive_bots {
+            self.bot_take_turn(i);
+        }
+
+        self.end_round();
+    }
+
+    // Runs one bot decision and reports anything it did to the activity log
+    fn bot_take_turn(&mut self, bot_index: usize) {
+        let before = self.bots[bot_index];
+        self.bot_make_decision(bot_index);
+        let after = self.bots[bot_index];
+        let name = after.display_name();
+
+        // Forget highlights that have finished fading
+        self.recent_bot_upgrades.retain(|(_, _, time)| time.elapsed() < BOT_UPGRADE_FLASH_DURATION);
+
+        for kind in UpgradeKind::ALL {
+            if after.level(kind) > before.level(kind) {
+                self.recent_bot_upgrades.push((bot_index, kind, Instant::now()));
+                self.activity_log.push_miner(
+                    bot_index + 1,
+                    ActivityKind::Bot,
+                    format!("{} upgraded {} to Lv{}", name, kind.label().to_lowercase(), after.level(kind)),
+                    self.round_elapsed()
+                );
+                self.bot_chatter(bot_index, ChatterEvent::Upgrade);
+            }
+        }
+
+        // Donation amounts stay hidden - the chatter only hints that one happened
+        if after.donated_gold > before.donated_gold {
+            self.bot_chatter(bot_index, ChatterEvent::Donate);
+        }
+    }
+
+    // Occasionally has a bot say a line matching its strategy, rate limited so the log isn't flooded
+    fn bot_chatter(&mut self, bot_index: usize, event: ChatterEvent) {
+        let strategy = match self.bots[bot_index].strategy {
+            Some(strategy) => strategy,
+            None => return,
+        };
+
+        // Always roll, before the cooldown check, so the seeded sequence doesn't depend on
+        // the wall-clock cooldown
+        let lines = chatter::lines(strategy, event);
+        let speaks = self.rng.gen_range(0.0..1.0) < CHATTER_CHANCE;
+        let line = lines[self.rng.gen_range(0..lines.len())];
+
+        let now = Instant::now();
+        if !speaks || self.last_chatter_time.is_some_and(|last| now.duration_since(last) < CHATTER_COOLDOWN) {
+            return;
+        }
+
+        self.activity_log.push_miner(bot_index + 1, ActivityKind::Chatter, line.replace("{}", strategy.name()), self.round_elapsed());
+        self.last_chatter_time = Some(now);
+    }
+
+    // Bookkeeping for every round start (new game, next round, or a continued save)
+    fn begin_round(&mut self) {
+        self.activity_log.push(ActivityKind::Round, format!("Round {} started", self.current_round), self.round_elapsed());
+        self.gold_earned_at_round_start = self.total_gold_earned;
+        self.rewind_point = None;
+    }
+
+    // Round time that has actually been played, excluding any paused time.
+    // All round timing should go through this rather than reading round_start_time directly.
+    pub fn round_elapsed(&self) -> Duration {
+        let now = Instant::now();
+        let current_pause = self.round_paused_at.map_or(Duration::ZERO, |paused_at| now.duration_since(paused_at));
+
+        now.duration_since(self.round_start_time)
+            .saturating_sub(self.round_paused_duration)
+            .saturating_sub(current_pause)
+    }
+
+    pub fn round_time_left(&self) -> Duration {
+        self.config.round_duration.saturating_sub(self.round_elapsed())
+    }
+
+    // Fill of the header's timer bar, from the played (pause-compensated) round time. As a
+    // round after the results screen starts, the bar sweeps back down from where the last
+    // round left it full instead of popping straight to empty.
+    pub fn timer_bar_progress(&self) -> f32 {
+        let progress = (self.round_elapsed().as_secs_f32() / self.config.round_duration.as_secs_f32()).clamp(0.0, 1.0);
+
+        let sweep = match self.timer_bar_reset_at {
+            Some(reset_at) if self.settings.animations => reset_at.elapsed().as_secs_f32() / TIMER_RESET_ANIMATION.as_secs_f32(),
+            _ => return progress,
+        };
+        if sweep >= 1.0 {
+            return progress;
+        }
+
+        // Eased so it slows as it lands on the live progress
+        let remaining = (1.0 - sweep) * (1.0 - sweep);
+        progress + (1.0 - progress) * remaining
+    }
+
+    pub fn round_timer_paused(&self) -> bool {
+        self.round_paused_at.is_some()
+    }
+
+    pub fn pause_round_timer(&mut self) {
+        if self.round_paused_at.is_none() {
+            self.round_paused_at = Some(Instant::now());
+        }
+    }
+
+    pub fn resume_round_timer(&mut self) {
+        if let Some(paused_at) = self.round_paused_at.take() {
+            self.round_paused_duration += paused_at.elapsed();
+        }
+    }
+
+    // Cheat: puts everyone's gold, donations and the round clock back to where they were
+    // when the final stretch began, to practise the end-of-round donations again
+    fn rewind_final_stretch(&mut self) {
+        let Some(point) = &self.rewind_point else {
+            return;
+        };
+
+        let now = Instant::now();
+        self.player = point.player;
+        self.bots = point.bots.clone();
+        self.total_gold_earned = point.total_gold_earned;
+
+        // Same clock adjustment as continuing a save, keeping a pause in place
+        self.round_start_time = now.checked_sub(point.elapsed).unwrap_or(now);
+        self.round_paused_duration = Duration::ZERO;
+        if self.round_paused_at.is_some() {
+            self.round_paused_at = Some(now);
+        }
+
+        self.flash_cheat("Rewound to the final stretch");
+        self.activity_log.push_miner(0, ActivityKind::Player, "Debug: rewound to the final stretch (cheat)".to_string(), self.round_elapsed());
+    }
+
+    // Starts a fresh, running round clock
+    fn reset_round_timer(&mut self) {
+        self.round_start_time = Instant::now();
+        self.round_paused_duration = Duration::ZERO;
+        self.round_paused_at = None;
+    }
+
+    // Moves each health bar toward its miner's real health. Runs while playing, so the damage
+    // from the last round drains away visibly once the results screen is dismissed.
+    fn ease_displayed_health(&mut self, dt: f32) {
+        let actual: Vec<f32> = std::iter::once(&self.player)
+            .chain(self.bots.iter())
+            .map(|miner| miner.health as f32)
+            .collect();
+
+        // Start (or restart) from the real values when the line-up changes
+        if self.displayed_health.len() != actual.len() {
+            self.displayed_health = actual;
+            return;
+        }
+
+        let step = (dt * HEALTH_EASE_RATE).min(1.0);
+        for (shown, target) in self.displayed_health.iter_mut().zip(actual) {
+            *shown += (target - *shown) * step;
+            if (target - *shown).abs() < 0.01 {
+                *shown = target;
+            }
+        }
+    }
+
+    // Health the bar for `miner_index` is currently showing (0 = player, i + 1 = bots[i])
+    pub fn displayed_health(&self, miner_index: usize) -> f32 {
+        let actual = if miner_index == 0 {
+            self.player.health
+        } else {
+            self.bots[miner_index - 1].health
+        };
+
+        self.displayed_health.get(miner_index).copied().unwrap_or(actual as f32)
+    }
+
+    // Multiplier on bot donations from the adaptive difficulty mode: lower while the player
+    // is low on health, higher while they keep winning rounds. Always 1.0 with the mode off.
+    pub fn adaptive_factor(&self) -> f32 {
+        if !self.config.adaptive_difficulty {
+            return 1.0;
+        }
+
+        let mut factor: f32 = 1.0;
+
+        if self.player.health <= self.player.critical_health() {
+            factor -= 0.2;
+        } else if self.player.health < self.player.low_health() {
+            factor -= 0.1;
+        }
+
+        // Wins in the last three rounds
+        let recent_wins = self.past_results.iter().rev().take(3).filter(|&&win| win).count();
+        factor += match recent_wins {
+            3 => 0.2,
+            2 => 0.1,
+            _ => 0.0,
+        };
+
+        // Bounded so bots never become pushovers or unbeatable
+        factor.clamp(0.75, 1.25)
+    }
+
+    // True in the final stretch of the round set by the donation lock option
+    pub fn donations_locked(&self) -> bool {
+        !self.config.turn_based
+            && !self.config.donation_lock.is_zero()
+            && self.round_time_left() <= self.config.donation_lock
+    }
+
+    // The clock only runs while actually playing with no overlay open
+    fn round_timer_should_pause(&self) -> bool {
+        self.settings_open() || self.draw_error.is_some() || !matches!(self.game_state, GameState::Playing)
+    }
+
+    // Pauses or resumes the round clock to match the current screen
+    fn sync_round_timer(&mut self) {
+        if self.round_timer_should_pause() {
+            self.pause_round_timer();
+        } else {
+            self.resume_round_timer();
+        }
+    }
+
+    // Cheapest purchase the player can't afford yet, as (label, cost).
+    // None means everything still available to buy is already affordable.
+    pub fn next_gold_goal(&self) -> Option<(String, f32)> {
+        let mut goals = Vec::new();
+
+        for kind in UpgradeKind::ALL {
+            if !self.player.maxed(kind) {
+                goals.push((format!("{} Lv{}", kind.label(), self.player.level(kind) + 1), self.player.upgrade_cost(kind)));
+            }
+        }
+        if self.pets.len() < MAX_PETS {
+            let name = if self.pets.is_empty() { "Pet Companion" } else { "Another Pet" };
+            goals.push((name.to_string(), self.pet_unlock_cost()));
+        }
+
+        goals
+            .into_iter()
+            .filter(|(_, cost)| *cost > self.player.gold)
+            .min_by(|a, b| a.1.partial_cmp(&b.1).unwrap())
+    }
+
+    // The standings if the round ended right now, with the damage each place would take.
+    // end_round applies exactly this, so the preview and the real result can't disagree.
+    pub fn project_standings(&self) -> Vec<Standing> {
+        // Collect all live miners' donated gold amounts (including player)
+        let mut results = Vec::new();
+        
+        // Add player (only missing from bots-only balance games)
+        if self.player.alive {
+            results.push((0, self.player.donated_gold));
+        }
+        
+        // Add bots
+        for (i, bot) in self.bots.iter().enumerate() {
+            if bot.alive {
+                results.push((i + 1, bot.donated_gold));
+            }
+        }
+        
+        // Sort by donated gold (highest first)
+        results.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap());
+
+        // A round where nobody donated has no real ranking: nobody takes damage,
+        // rather than punishing whoever the sort put last. The first round shield
+        // skips damage too, though the ranking (and round win) still count.
+        let no_damage = nobody_donated(&results) || self.first_round_shielded();
+        let miner_count = results.len();
+
+        results
+            .into_iter()
+            .enumerate()
+            .map(|(position, (miner_index, donated))| Standing {
+                miner_index,
+                donated,
+                damage: if no_damage {
+                    0
+                } else {
+                    self.config.damage_model.damage_for(position, miner_count)
+                },
+            })
+            .collect()
+    }
+
+    // A live donation as the opponents panel and standings show it: gold, or the share of
+    // everything donated this round by miners still alive
+    pub fn donation_label(&self, donated: f32) -> String {
+        if !self.show_donation_share {
+            return format!("{}g", self.format_gold(donated));
+        }
+
+        let total: f32 = std::iter::once(&self.player)
+            .chain(self.bots.iter())
+            .filter(|miner| miner.alive)
+            .map(|miner| miner.donated_gold)
+            .sum();
+
+        if total > 0.0 {
+            format!("{:.0}%", donated / total * 100.0)
+        } else {
+            "—".to_string()
+        }
+    }
+
+    // Gold amount at the precision picked in settings (whole numbers by default)
+    pub fn format_gold(&self, amount: f32) -> String {
+        self.settings.format_gold(amount)
+    }
+
+    // True when the first round shield option spares everyone this round
+    pub fn first_round_shielded(&self) -> bool {
+        self.config.first_round_shield && self.current_round == 1
+    }
+
+    // Rounds still to come after this one; 0 on the final round, when surviving it ends the game
+    pub fn rounds_remaining(&self) -> usize {
+        self.config.round_count.saturating_sub(self.current_round)
+    }
+
+    // Damage the player would take if the round ended now, from the live donations
+    pub fn player_projected_damage(&self) -> i32 {
+        self.project_standings()
+            .iter()
+            .find(|standing| standing.miner_index == 0)
+            .map_or(0, |standing| standing.damage)
+    }
+
+    // Indices into `bots` in the order the opponents panel lists them. Only the
+    // display is sorted - `bots` itself keeps its order since end_round indexes into it.
+    pub fn opponent_display_order(&self) -> Vec<usize> {
+        let mut order: Vec<usize> = (0..self.bots.len())
+            .filter(|&i| self.show_dead_bots || self.bots[i].alive)
+            .collect();
+
+        if let Some(sort) = self.opponent_sort {
+            let key = |i: &usize| {
+                let bot = &self.bots[*i];
+                match sort {
+                    OpponentSort::Health => bot.health as f32,
+                    OpponentSort::Gold => bot.gold,
+                    OpponentSort::Donated => bot.donated_gold,
+                }
+            };
+
+            // Stable, so ties keep the line-up order
+            order.sort_by(|a, b| key(b).partial_cmp(&key(a)).unwrap());
+        }
+
+        order
+    }
+
+    // Price of the next pet: the difficulty's base price, times how many pets that makes.
+    // Shown on the unlock button and deducted by unlock_pet, so the two always match.
+    pub fn pet_unlock_cost(&self) -> f32 {
+        self.config.difficulty.pet_unlock_cost() * (self.pets.len() + 1) as f32
+    }
+
+    pub fn unlock_pet(&mut self) {
+        let cost = self.pet_unlock_cost();
+        if self.pets.len() < MAX_PETS && self.player.gold >= cost {
+            self.player.gold -= cost;
+
+            // Each new pet starts on the next color after the saved one, so they're easy to tell apart
+            let mut pet = Pet {
+                color: self.settings.pet_color.after(self.pets.len()),
+                ..Pet::new()
+            };
+            pet.unlock();
+            self.pets.push(pet);
+            self.activity_log.push_miner(0, ActivityKind::Player, format!("You unlocked a pet for {:.0}g", cost), self.round_elapsed());
+        }
+    }
+
+    // A dead pet can be bought back for PET_REVIVE_COST
+    pub fn can_revive_pet(&self, index: usize) -> bool {
+        self.pets.get(index).is_some_and(|pet| pet.unlocked && !pet.alive)
+            && self.player.gold >= PET_REVIVE_COST
+    }
+
+    pub fn revive_pet(&mut self, index: usize) {
+        if self.can_revive_pet(index) {
+            self.player.gold -= PET_REVIVE_COST;
+            self.pets[index].revive();
+            self.activity_log.push_miner(0, ActivityKind::Player, format!("You revived a pet for {:.0}g", PET_REVIVE_COST), self.round_elapsed());
+        }
+    }
+
+    pub fn toggle_pet_mining(&mut self, index: usize) {
+        if let Some(pet) = self.pets.get_mut(index) {
+            pet.toggle_mining();
+        }
+    }
+
+    pub fn toggle_pet_searching(&mut self, index: usize) {
+        if let Some(pet) = self.pets.get_mut(index) {
+            pet.toggle_searching();
+        }
+    }
+
+    // The oldest live pet takes the hit for the player. Returns false if no pet is left to take it.
+    pub fn pet_take_hit(&mut self) -> bool {
+        match self.pets.iter_mut().find(|pet| pet.alive) {
+            Some(pet) => {
+                pet.take_hit();
+                pet.hits_absorbed += 1;
+                if self.counts_for_stats() {
+                    self.lifetime_stats.record_pet_lost();
+                }
+                true
+            },
+            None => false,
+        }
+    }
+
+    pub fn end_round(&mut self) {
+        // Freeze the clock while the results are up
+        self.pause_round_timer();
+        // The comparison table and bot popup are only drawn in play, so they mustn't keep blocking clicks
+        self.close_overlay(Overlay::Comparison);
+        self.close_bot_detail();
+        self.donate_all_armed_at = None;
+
+        // The round's played time, excluding pauses (a skip cheat can't push it past the round length)
+        self.time_played += self.round_elapsed().min(self.config.round_duration);
+
+        // Last round's ranking becomes the one to compare against
+        self.previous_round_ranking = std::mem::take(&mut self.last_round_ranking);
+
+        // Rank everyone by donations and work out the damage for each place
+        let standings = self.project_standings();
+        self.last_round_ranking = standings.iter().map(|standing| standing.miner_index).collect();
+        let results: Vec<(usize, f32)> = standings
+            .iter()
+            .map(|standing| (standing.miner_index, standing.donated))
+            .collect();
+
+        // Record if the player won this round (was ranked #1). Nobody wins a round nobody donated in.
+        let player_won = !nobody_donated(&results) && results.first().is_some_and(|(index, _)| *index == 0);
+        self.past_results.push(player_won);
+
+        // The top donor is the round's MVP (nobody is if nobody donated)
+        if !nobody_donated(&results)
+            && let Some(&(mvp_index, _)) = results.first()
+        {
+            if mvp_index == 0 {
+                self.player.mvp_rounds += 1;
+            } else {
+                self.bots[mvp_index - 1].mvp_rounds += 1;
+            }
+        }
+        
+        // Remember who was alive so deaths can be logged
+        let player_was_alive = self.player.alive;
+        let player_health_before = self.player.health;
+        let live_pets_before = self.pets.iter().filter(|pet| pet.alive).count();
+        let bots_were_alive: Vec<bool> = self.bots.iter().map(|bot| bot.alive).collect();
+
+        // The most the player could have been hit for: whatever last place took (nothing in
+        // a shielded round or one nobody donated in)
+        if player_was_alive {
+            self.max_possible_damage += standings.iter().map(|standing| standing.damage).max().unwrap_or(0);
+        }
+
+        // Assign damage based on position and the chosen damage model,
+        // keeping each step of the calculation for the results screen
+        let waived = if nobody_donated(&results) {
+            Some("nobody donated")
+        } else if self.first_round_shielded() {
+            Some("round 1 shield")
+        } else {
+            None
+        };
+        self.round_damage.clear();
+
+        // A lost round, for the round memory: finishing in the bottom half of the field (so last
+        // place at worst, whatever the damage model). Nobody loses a round nobody donated in.
+        let first_losing_place = if nobody_donated(&results) { usize::MAX } else { standings.len().div_ceil(2) };
+
+        for (position, standing) in standings.iter().enumerate() {
+            let lost = position >= first_losing_place;
+            let damage = standing.damage;
+            let mut pet_absorbed = false;
+            
+            if standing.miner_index == 0 {
+                // Player - a live pet takes the hit instead, if there is one
+                pet_absorbed = damage > 0 && self.pet_take_hit();
+                if !pet_absorbed {
+                    self.player.take_damage(damage);
                 }
+                // The nemesis reads the player's recent donations
+                self.player.round_memory.record(lost, standing.donated);
+            } else {
+                // Bot - remembering whether this round went against it
+                let bot = &mut self.bots[standing.miner_index - 1];
+                bot.take_damage(damage);
+                bot.round_memory.record(lost, standing.donated);
+            }
+
+            self.round_damage.push(DamageBreakdown {
+                placement: position as i32,
+                model: self.config.damage_model.damage_for(position, standings.len()),
+                waived,
+                pet_absorbed,
+                taken: if pet_absorbed { 0 } else { damage },
+            });
+        }
+        
+        for (i, bot) in self.bots.iter().enumerate() {
+            if bots_were_alive[i] && !bot.alive {
+                self.activity_log.push_miner(i + 1, ActivityKind::Death, format!("{} has died!", bot.display_name()), self.round_elapsed());
+                self.elimination_order.push(i + 1);
+            }
+        }
+
+        if player_was_alive && !self.player.alive {
+            self.activity_log.push_miner(0, ActivityKind::Death, "You have died!".to_string(), self.round_elapsed());
+            self.elimination_order.push(0);
+        }
+
+        self.nemesis_round_end_line();
+
+        if self.first_blood_round.is_none() && !self.elimination_order.is_empty() {
+            self.first_blood_round = Some(self.current_round);
+        }
+
+        // Short personal summary for the top of the results screen
+        if let Some(position) = standings.iter().position(|standing| standing.miner_index == 0) {
+            let damage_taken = player_health_before - self.player.health;
+            let damage_note = if self.pets.iter().filter(|pet| pet.alive).count() < live_pets_before {
+                "your pet took the hit".to_string()
+            } else {
+                format!("took {} damage", damage_taken)
+            };
+
+            self.round_recap = Some((
+                format!(
+                    "You placed #{}, {}, earned {}g",
+                    position + 1,
+                    damage_note,
+                    self.format_gold(self.total_gold_earned - self.gold_earned_at_round_start)
+                ),
+                Instant::now(),
+            ));
+        }
+
+        // Results are recorded, so clear the per-round state for everyone
+        self.player.reset_for_new_round();
+        for bot in &mut self.bots {
+            bot.reset_for_new_round();
+        }
+        
+        // Store results for display
+        self.round_results = Some(results);
+        self.audio.queue(Sound::RoundEnd);
+        
+        // Check win/loss conditions
+        
+        // Check if player died this round
+        if player_was_alive && !self.player.alive {
+            self.finish_game();
+            return;
+        }
+        
+        // Check if all bots are dead
+        let bots_alive = self.bots.iter().any(|bot| bot.alive);
+        if !bots_alive {
+            self.finish_game();
+            return;
+        }
+        
+        // Check if max rounds reached
+        if self.current_round >= self.config.round_count {
+            self.finish_game();
+            return;
+        }
+        
+        // If we're still here, continue to the next round
+        self.game_state = GameState::RoundEnd;
+    }
+    
+    // Games with cheats or in practice mode aren't recorded in the lifetime stats
+    fn counts_for_stats(&self) -> bool {
+        !self.cheats_used && !self.config.sandbox && !self.headless
+    }
+
+    fn finish_game(&mut self) {
+        self.game_state = GameState::GameOver;
+        self.game_over_at = Some(Instant::now());
+        if self.player_has_won() {
+            self.audio.queue(Sound::Victory);
+        }
+
+        // Only outlasting every bot counts as a win (hardcore too), and every pet still alive as
+        // one that survived. A game where no pet was unlocked adds nothing to the pet counts.
+        if self.counts_for_stats() {
+            self.lifetime_stats.record_game(self.player_has_won(), self.config.hardcore);
+            let pets_alive = self.pets.iter().filter(|pet| pet.alive).count();
+            self.lifetime_stats.record_pets_survived(pets_alive as u32);
+        }
+
+        // The run is over - don't offer to continue it
+        if !self.headless {
+            save::delete_save();
+        }
+        self.save_available = false;
+    }
+
+    // Narrowest gap between neighbouring places in the last round's results, if it
+    // was close enough to call. Returns (upper position, gap); the pair is position and position + 1.
+    pub fn closest_call(&self) -> Option<(usize, f32)> {
+        let results = self.round_results.as_ref()?;
+
+        results
+            .windows(2)
+            .enumerate()
+            .filter(|(_, pair)| pair[0].1 > 0.0) // nobody donating isn't a race
+            .map(|(position, pair)| (position, pair[0].1 - pair[1].1))
+            .filter(|(_, gap)| *gap <= PHOTO_FINISH_MARGIN)
+            .min_by(|a, b| a.1.partial_cmp(&b.1).unwrap())
+    }
+
+    // A lost hardcore run is final: no restarting it from the game over screen, only going back to the menu
+    pub fn restart_locked(&self) -> bool {
+        self.config.hardcore && !self.player.alive
+    }
+
+    // Time since the game ended, for the end screen animations
+    pub fn game_over_elapsed(&self) -> Duration {
+        self.game_over_at.map_or(Duration::ZERO, |time| time.elapsed())
+    }
+
+    pub fn player_has_won(&self) -> bool {
+        // Player wins if they're alive and all bots are dead
+        self.player.alive && !self.bots.iter().any(|bot| bot.alive)
+    }
+
+    pub fn start_next_round(&mut self) {
+        // Apply the round end choice before the new round starts
+        if self.cash_in_selected && self.can_cash_in() {
+            self.player.gold -= CASH_IN_COST;
+            self.player.health += 1;
+            self.activity_log.push_miner(0, ActivityKind::Player, format!("You cashed in {:.0}g for 1 health", CASH_IN_COST), self.round_elapsed());
+        }
+        self.cash_in_selected = false;
+
+        // Everyone's starting position, shown over the first few seconds of the round
+        let snapshot = std::iter::once(&self.player)
+            .chain(self.bots.iter())
+            .enumerate()
+            .filter(|(_, miner)| miner.alive)
+            .map(|(miner_index, miner)| MinerSnapshot { miner_index, gold: miner.gold, health: miner.health })
+            .collect();
+        self.round_start_snapshot = Some((snapshot, Instant::now()));
+
+        self.current_round += 1;
+        self.reset_round_timer();
+        self.timer_bar_reset_at = Some(Instant::now());
+        self.game_state = GameState::Playing;
+        self.round_results = None;
+
+        self.begin_round();
+        self.start_turn();
+        self.auto_save();
+    }
+
+    pub fn auto_save(&mut self) {
+        if self.headless {
+            return;
+        }
+
+        self.save_data().write();
+        self.save_available = true;
+    }
+
+    // Everything needed to pick the game back up from this moment
+    fn save_data(&self) -> SaveData {
+        SaveData {
+            player: self.player,
+            bots: self.bots.clone(),
+            pets: self.pets.clone(),
+            pet: None,
+            time_played: self.time_played.as_secs_f32(),
+            max_possible_damage: self.max_possible_damage,
+            config: self.config,
+            current_round: self.current_round,
+            past_results: self.past_results.clone(),
+            total_gold_earned: self.total_gold_earned,
+            seed: self.seed,
+            elimination_order: self.elimination_order.clone(),
+            first_blood_round: self.first_blood_round,
+            round_time_left: self.round_time_left().as_secs_f32(),
+            rng_word_pos: self.rng.get_word_pos(),
+            cheats_used: self.cheats_used,
+        }
+    }
+
+    // Resumes the auto-saved game, picking the round clock back up where it was saved
+    pub fn continue_saved_game(&mut self) {
+        let data = match SaveData::load() {
+            Some(data) => data,
+            None => {
+                self.save_available = false;
+                return;
+            }
+        };
+        self.resume_game(data);
+    }
+
+    fn resume_game(&mut self, data: SaveData) {
+        // Clear the last game's per-game state, then overlay the save. Nothing is spawned,
+        // seeded or written, so the save file stays as it was.
+        self.config = data.config;
+        self.reset_game_state();
+
+        self.player = data.player;
+        self.bots = data.bots;
+        for miner in std::iter::once(&mut self.player).chain(self.bots.iter_mut()) {
+            miner.restore_legacy_levels();
+        }
+        // Saves from before miners had their own color give every bot the player's
+        for (i, bot) in self.bots.iter_mut().enumerate() {
+            if bot.color == PLAYER_COLOR {
+                bot.color = BotStrategy::for_index(i).color();
+            }
+        }
+        self.displayed_health.clear(); // snap the bars to the loaded health
+        self.time_played = Duration::from_secs_f32(data.time_played.max(0.0));
+        self.max_possible_damage = data.max_possible_damage;
+        self.pets = data.pets;
+        if let Some(pet) = data.pet.filter(|pet| pet.unlocked)
+            && self.pets.is_empty()
+        {
+            self.pets.push(pet);
+        }
+        self.current_round = data.current_round;
+        self.past_results = data.past_results;
+        self.total_gold_earned = data.total_gold_earned;
+        self.seed = data.seed;
+        self.elimination_order = data.elimination_order;
+        self.first_blood_round = data.first_blood_round;
+        self.cheats_used = data.cheats_used;
+        // Pick the random stream up where it was saved, so a continued game plays out
+        // the same as if it hadn't been interrupted
+        self.rng = ChaCha12Rng::seed_from_u64(data.seed);
+        self.rng.set_word_pos(data.rng_word_pos);
+
+        let time_left = Duration::from_secs_f32(data.round_time_left.max(0.0));
+        let elapsed = self.config.round_duration.saturating_sub(time_left);
+        self.round_start_time = Instant::now().checked_sub(elapsed).unwrap_or_else(Instant::now);
+
+        self.begin_round();
+    }
+
+    pub fn restart_game(&mut self) {
+        self.reset_game_state();
+        self.player = spawn_player(&self.config);
+        self.bots = spawn_bots(&self.config);
+        self.current_round = 1;
+        // Start without pets again. The Free Pet option gives one straight away,
+        // in the color the player picked for their first pet (saved with the settings).
+        if self.config.free_pet {
+            self.pets.push(Pet {
+                color: self.settings.pet_color,
+                unlocked: true,
+                ..Pet::new()
+            });
+        }
+        // Use the seed from the menu field once, otherwise roll a new one
+        self.seed = self.seed_input.parse().unwrap_or_else(|_| rand::random());
+        self.seed_input.clear();
+        self.rng = ChaCha12Rng::seed_from_u64(self.seed);
+        self.designate_nemesis();
+        self.apply_start_round();
+        self.begin_round();
+        self.start_turn();
+        self.auto_save();
+    }
+
+    // Per-game state a new game and a continued one both start from
+    fn reset_game_state(&mut self) {
+        self.reset_round_timer();
+        self.game_state = GameState::Playing;
+        self.round_results = None;
+        self.past_results = Vec::new();
+        self.total_gold_earned = 0.0;
+        self.pets.clear();
+        self.show_cursor_position = false;
+        self.cursor_position = (0.0, 0.0);
+        self.hover_tooltip = None;
+        self.tooltip_warm_until = None;
+        self.seed_input_focused = false;
+        self.activity_log.clear();
+        self.last_chatter_time = None;
+        self.upgrade_feedback = None;
+        self.displayed_health.clear(); // new line-up, so the bars start full instead of animating
+        self.cheats_used = false;
+        self.recent_bot_upgrades.clear();
+        self.last_upgrade = None;
+        self.elimination_order.clear();
+        self.first_blood_round = None;
+        self.last_round_ranking.clear();
+        self.previous_round_ranking.clear();
+        self.round_recap = None;
+        self.round_start_snapshot = None;
+        self.time_played = Duration::ZERO;
+        self.max_possible_damage = 0;
+    }
+
+    // Tooltip text for whatever gameplay element is at (x, y), matching the rects drawn in ui.rs
+    pub fn tooltip_at(&self, x: f32, y: f32) -> Option<String> {
+        let timer_tooltip = if self.config.turn_based {
+            "End your turn: the bots make their moves\nand players are ranked by gold donated."
+        } else {
+            "Time left in the round. When it runs out,\nplayers are ranked by gold donated."
+        };
+        // Who takes damage under the chosen model, and whether this round is spared
+        let mut damage_rule = self.config.damage_model.description().to_string();
+        if self.first_round_shielded() {
+            damage_rule.push_str("\nRound 1 shield: nobody takes damage this round.");
+        }
+        let mut regions = vec![
+            (Rect::new(200.0, 30.0, 300.0, 20.0), timer_tooltip.to_string()),
+            (Rect::new(20.0, 90.0, 220.0, 30.0),
+             "Gold on hand. Spend it on upgrades\nor donate it to win the round.".to_string()),
+            (Rect::new(20.0, 125.0, 220.0, 30.0),
+             format!("You lose health at the end of each round:\n{}\nThe game ends when it reaches 0.", damage_rule)),
+            (Rect::new(WINDOW_WIDTH - 240.0, 150.0, 220.0, 25.0),
+             "Gold you have donated this round.\nIt resets when the round ends.".to_string()),
+        ];
+
+        for (i, kind) in UpgradeKind::ALL.iter().enumerate() {
+            regions.push((upgrade_button_rect(i), kind.effect().to_string()));
+        }
+
+        // The fixed donation tiers plus "All"
+        for i in 0..=DONATION_TIERS.len() {
+            regions.push((
+                donation_button_rect(i),
+                format!("Donated gold is spent for good and ranks\nyou for the round:\n{}", damage_rule),
+            ));
+        }
+
+        regions
+            .into_iter()
+            .find(|(rect, _)| rect.contains([x, y]))
+            .map(|(_, text)| text)
+    }
+
+    // Tooltip explaining the damage in the results row under the cursor
+    pub fn damage_tooltip_at(&self, x: f32, y: f32) -> Option<String> {
+        let position = (0..self.round_damage.len())
+            .find(|&position| self.round_end_damage_rect(position).contains([x, y]))?;
+        let breakdown = &self.round_damage[position];
+
+        let mut lines = vec![
+            format!("Placed #{}: {} damage", position + 1, breakdown.placement),
+            format!("{} damage: {}", self.config.damage_model.label(), breakdown.model),
+        ];
+        if let Some(reason) = breakdown.waived {
+            lines.push(format!("Waived: {}", reason));
+        }
+        if breakdown.pet_absorbed {
+            lines.push("Absorbed by your pet".to_string());
+        }
+        lines.push(format!("Taken: {}", breakdown.taken));
+
+        Some(lines.join("\n"))
+    }
+
+    pub fn update_hover(&mut self, x: f32, y: f32) {
+        let target = match self.game_state {
+            GameState::Playing => self.tooltip_at(x, y),
+            GameState::RoundEnd => self.damage_tooltip_at(x, y),
+            _ => None,
+        };
+
+        if target == self.hover_tooltip {
+            return;
+        }
+
+        let now = Instant::now();
+
+        // Leaving a visible tooltip briefly lets the next one skip the delay
+        if self.tooltip_visible() {
+            self.tooltip_warm_until = Some(now + TOOLTIP_GRACE);
+        }
+
+        let warm = self.tooltip_warm_until.is_some_and(|until| now < until);
+        self.hover_tooltip = target;
+        self.hover_start = if warm {
+            now.checked_sub(TOOLTIP_DELAY).unwrap_or(now)
+        } else {
+            now
+        };
+    }
+
+    pub fn tooltip_visible(&self) -> bool {
+        self.hover_tooltip.is_some() && self.hover_start.elapsed() >= TOOLTIP_DELAY
+    }
+
+    pub fn handle_game_ui_click(&mut self, x: f32, y: f32) {
+        // Clicking anywhere but Donate All cancels a pending Donate All
+        let on_donate_all = donation_button_rect(DONATION_TIERS.len()).contains([x, y]);
+        if !on_donate_all {
+            self.donate_all_armed_at = None;
+        }
+
+        // Check settings button
+        if settings_button_rect().contains([x, y]) {
+            self.open_overlay(Overlay::Settings);
+            return;
+        }
+
+        if self.config.turn_based && end_turn_button_rect().contains([x, y]) {
+            self.end_turn();
+            return;
+        }
+
+        // Opponents panel: clicking a sort header sorts by it, clicking it again goes back to line-up order
+        for (i, sort) in OpponentSort::ALL.iter().enumerate() {
+            if opponent_sort_rect(i).contains([x, y]) {
+                self.opponent_sort = if self.opponent_sort == Some(*sort) { None } else { Some(*sort) };
+                return;
+            }
+        }
+
+        if dead_bots_toggle_rect().contains([x, y]) {
+            self.show_dead_bots = !self.show_dead_bots;
+            return;
+        }
+
+        if let Some(bot_index) = self.opponent_row_at(x, y) {
+            self.open_bot_detail(bot_index);
+            return;
+        }
+
+        // Upgrade buttons
+        for (i, kind) in UpgradeKind::ALL.iter().enumerate() {
+            if upgrade_button_rect(i).contains([x, y]) {
+                if self.player.upgrade(*kind) {
+                    self.last_upgrade = Some(LastUpgrade {
+                        kind: *kind,
+                        level: self.player.level(*kind),
+                        round: self.current_round,
+                        time_left: self.round_time_left(),
+                    });
+                    self.audio.queue(Sound::Upgrade);
+                    self.activity_log.push_miner(
+                        0,
+                        ActivityKind::Player,
+                        format!("You upgraded {} to Lv{}", kind.label().to_lowercase(), self.player.level(*kind)),
+                        self.round_elapsed()
+                    );
+                } else {
+                    self.upgrade_blocked(*kind, self.player.maxed(*kind));
+                }
+            }
+        }
+        
+        // Check numeric contribution options
+        for (i, amount) in DONATION_TIERS.iter().enumerate() {
+            if donation_button_rect(i).contains([x, y]) {
+                self.donate(*amount);
+                break;
+            }
+        }
+        
+        // Check "All" option, the button after the tiers
+        if on_donate_all {
+            self.request_donate_all();
+        }
+        
+        // Pet color swatches. The first pet's color is remembered for the next games.
+        for (pet_index, pet) in self.pets.iter_mut().enumerate() {
+            if !pet.alive {
+                continue;
+            }
+            for (i, color) in PetColor::ALL.iter().enumerate() {
+                if pet_swatch_rect(pet_index, i).contains([x, y]) {
+                    pet.color = *color;
+                    if pet_index == 0 && self.settings.pet_color != *color {
+                        self.settings.pet_color = *color;
+                        self.settings.save();
+                    }
+                    break;
+                }
+            }
+        }
+
+        if self.pets.len() < MAX_PETS && pet_unlock_button_rect(self.pets.len()).contains([x, y]) {
+            self.unlock_pet();
+        }
+
+        // Pet abilities, per pet; a dead pet only has its revive button
+        for index in 0..self.pets.len() {
+            if !self.pets[index].alive {
+                if pet_revive_button_rect(index).contains([x, y]) {
+                    self.revive_pet(index);
+                }
+                continue;
+            }
+            if pet_mine_button_rect(index).contains([x, y]) {
+                self.toggle_pet_mining(index);
+            } else if pet_search_button_rect(index).contains([x, y]) {
+                self.toggle_pet_searching(index);
+            }
+        }
+    }
+
+    // Donate All goes through on the second press; the first one only arms it
+    fn request_donate_all(&mut self) {
+        if self.donate_all_armed() {
+            self.donate_all_armed_at = None;
+            self.donate(self.player.gold);
+        } else if self.player.gold > 0.0 {
+            self.donate_all_armed_at = Some(Instant::now());
+        }
+    }
+
+    // Donate All was pressed once and is waiting for confirmation
+    pub fn donate_all_armed(&self) -> bool {
+        self.donate_all_armed_at.is_some_and(|time| time.elapsed() < DONATE_ALL_CONFIRM_WINDOW)
+    }
+
+    // Player donation from a button or number key; does nothing if it can't be afforded
+    fn donate(&mut self, amount: f32) {
+        if amount > 0.0 && amount <= self.player.gold {
+            self.player.contribute_gold(amount);
+            self.audio.queue(Sound::Donate);
+        }
+    }
+
+    fn upgrade_blocked(&mut self, kind: UpgradeKind, maxed: bool) {
+        let message = if maxed { "Max level" } else { "Not enough gold" };
+
+        self.upgrade_feedback = Some(UpgradeFeedback {
+            kind,
+            message,
+            time: Instant::now(),
+        });
+    }
+
+    // The blocked-upgrade message for `kind`, while it's still showing
+    pub fn active_upgrade_feedback(&self, kind: UpgradeKind) -> Option<&UpgradeFeedback> {
+        self.upgrade_feedback
+            .as_ref()
+            .filter(|feedback| feedback.kind == kind && feedback.time.elapsed() < UPGRADE_FEEDBACK_DURATION)
+    }
+
+    // Strength (1.0 fading to 0.0) of the highlight on a bot's level indicator after it upgraded
+    pub fn bot_upgrade_highlight(&self, bot_index: usize, kind: UpgradeKind) -> Option<f32> {
+        self.recent_bot_upgrades
+            .iter()
+            .filter(|(index, upgrade, _)| *index == bot_index && *upgrade == kind)
+            .map(|(_, _, time)| time.elapsed())
+            .filter(|elapsed| *elapsed < BOT_UPGRADE_FLASH_DURATION)
+            .map(|elapsed| 1.0 - elapsed.as_secs_f32() / BOT_UPGRADE_FLASH_DURATION.as_secs_f32())
+            .reduce(f32::max)
+    }
+
+    // Time between a mining pet's gold payouts: half the player's speed
+    pub fn pet_mine_rate(&self) -> Duration {
+        self.player.mine_rate() * 2
+    }
+
+    // How close mining pet `index` is to its next payout (0.0 just paid, up to 1.0), None unless it's mining
+    pub fn pet_mine_progress(&self, index: usize) -> Option<f32> {
+        let pet = self.pets.get(index).filter(|pet| pet.alive && pet.mining)?;
+        Some((pet.progress.as_secs_f32() / self.pet_mine_rate().as_secs_f32()).min(1.0))
+    }
+
+    // How far through pet `index`'s loot sparkle we are (0.0 just found, up to 1.0), while it's showing
+    pub fn pet_loot_sparkle(&self, index: usize) -> Option<f32> {
+        self.pets.get(index)?
+            .loot_found_at
+            .map(|time| time.elapsed())
+            .filter(|elapsed| *elapsed < PET_LOOT_SPARKLE_DURATION)
+            .map(|elapsed| elapsed.as_secs_f32() / PET_LOOT_SPARKLE_DURATION.as_secs_f32())
+    }
+
+    // How strong the low-health vignette is (0.0 to 1.0), None while it's off. Follows the
+    // animated health so it eases out as health recovers, and is gone once past critical health.
+    pub fn low_health_vignette_strength(&self) -> Option<f32> {
+        if !self.settings.low_health_vignette || !self.player.alive {
+            return None;
+        }
+
+        let health = self.displayed_health(0);
+        let critical = self.player.critical_health() as f32;
+        let strength = ((critical + 1.0 - health) / critical).clamp(0.0, 1.0);
+        if strength > 0.0 { Some(strength) } else { None }
+    }
+
+    // Gold donated per point of damage the player avoided over the game (lower is better).
+    // None when no damage was avoided, so there's nothing to divide by.
+    pub fn donation_efficiency(&self) -> Option<f32> {
+        let avoided = self.max_possible_damage - self.player.total_damage_taken;
+        if avoided > 0 {
+            Some(self.player.total_donated / avoided as f32)
+        } else {
+            None
+        }
+    }
+
+    // Places gained (positive) or lost since the previous round, None if they weren't ranked then
+    pub fn rank_change(&self, miner_index: usize, position: usize) -> Option<i32> {
+        self.previous_round_ranking
+            .iter()
+            .position(|&index| index == miner_index)
+            .map(|previous| previous as i32 - position as i32)
+    }
+
+    // The recap text and how visible it is (1.0 fading to 0.0), while it's showing
+    pub fn round_recap_fade(&self) -> Option<(&str, f32)> {
+        let (text, time) = self.round_recap.as_ref()?;
+        let elapsed = time.elapsed();
+        if elapsed >= ROUND_RECAP_DURATION {
+            return None;
+        }
+
+        // Fully visible for most of its time, fading out over the last half second
+        let remaining = (ROUND_RECAP_DURATION - elapsed).as_secs_f32();
+        Some((text.as_str(), (remaining / 0.5).min(1.0)))
+    }
+
+    // Shows a short confirmation that a cheat key did something, and keeps the game out
+    // of the lifetime stats
+    fn flash_cheat(&mut self, text: &str) {
+        self.cheat_flash = Some((text.to_string(), Instant::now()));
+        self.cheats_used = true;
+    }
+
+    // The cheat confirmation and its opacity while it's showing, fading out over its last half second
+    pub fn cheat_flash_fade(&self) -> Option<(&str, f32)> {
+        let (text, time) = self.cheat_flash.as_ref()?;
+        let elapsed = time.elapsed();
+        if elapsed >= CHEAT_FLASH_DURATION {
+            return None;
+        }
+
+        let remaining = (CHEAT_FLASH_DURATION - elapsed).as_secs_f32();
+        Some((text.as_str(), (remaining / 0.5).min(1.0)))
+    }
+
+    // The round start snapshot and its opacity while it's showing, fading out over its last second
+    pub fn round_start_snapshot_fade(&self) -> Option<(&[MinerSnapshot], f32)> {
+        let (snapshot, time) = self.round_start_snapshot.as_ref()?;
+        let elapsed = time.elapsed();
+        if elapsed >= ROUND_START_SNAPSHOT_DURATION {
+            return None;
+        }
+
+        let remaining = (ROUND_START_SNAPSHOT_DURATION - elapsed).as_secs_f32();
+        Some((snapshot.as_slice(), remaining.min(1.0)))
+    }
+
+    // Cashing in needs the gold and some missing health to restore
+    pub fn can_cash_in(&self) -> bool {
+        self.player.gold >= CASH_IN_COST && self.player.health < self.player.max_health
+    }
+
+    // The upgrade the results screen offers to undo: bought in the last UPGRADE_UNDO_WINDOW
+    // of the round that just ended, and still the top level of its kind. Turn-based rounds
+    // have no clock to run out on, so they never offer one.
+    pub fn undoable_upgrade(&self) -> Option<&LastUpgrade> {
+        if !matches!(self.game_state, GameState::RoundEnd) || self.round_results.is_none() || self.config.turn_based {
+            return None;
+        }
+
+        self.last_upgrade.as_ref().filter(|upgrade| {
+            upgrade.round == self.current_round
+                && upgrade.time_left <= UPGRADE_UNDO_WINDOW
+                && self.player.level(upgrade.kind) == upgrade.level
+        })
+    }
+
+    // Takes back the wasted upgrade with a full refund
+    fn undo_last_upgrade(&mut self) {
+        let Some(kind) = self.undoable_upgrade().map(|upgrade| upgrade.kind) else {
+            return;
+        };
+
+        let refund = self.player.undo_upgrade(kind);
+        self.last_upgrade = None;
+        self.activity_log.push_miner(
+            0,
+            ActivityKind::Player,
+            format!("You undid your {} upgrade (+{}g)", kind.label().to_lowercase(), self.format_gold(refund)),
+            self.round_elapsed()
+        );
+    }
+
+    pub fn handle_round_end_ui_click(&mut self, x: f32, y: f32) {
+        if self.round_end_undo_rect().contains([x, y]) && self.undoable_upgrade().is_some() {
+            self.undo_last_upgrade();
+            return;
+        }
+
+        if self.round_results.is_some() {
+            if self.round_end_keep_rect().contains([x, y]) {
+                self.cash_in_selected = false;
+            } else if self.round_end_cash_in_rect().contains([x, y]) && self.can_cash_in() {
+                self.cash_in_selected = true;
+            }
+        }
+
+        if self.round_end_continue_rect().contains([x, y]) {
+            self.start_next_round();
+        }
+    }
+
+    pub fn handle_settings_click(&mut self, x: f32, y: f32) {
+        for (i, channel) in VolumeChannel::ALL.iter().enumerate() {
+            if self.volume_slider_hit_rect(i).contains([x, y]) {
+                self.dragging_slider = Some(*channel);
+                self.drag_slider_to(x);
+                return;
+            }
+        }
+
+        if let Some(button) = self.settings_button_rects().iter().position(|rect| rect.contains([x, y])) {
+            self.activate_settings_button(button);
+        }
+    }
+
+    // The buttons under the volume sliders: the option rows (left column, then right), Reset
+    // Stats and Close. Quit to Menu comes last, and only while a game is in progress.
+    pub fn settings_button_rects(&self) -> Vec<Rect> {
+        let mut rects = vec![
+            self.animations_toggle_rect(),
+            self.layout_toggle_rect(),
+            self.auto_upgrade_toggle_rect(),
+            self.auto_upgrade_reserve_rect(),
+            self.gold_decimals_rect(),
+            self.low_health_vignette_rect(),
+            self.activity_timestamps_rect(),
+            self.reveal_strategies_rect(),
+            self.activity_log_toggle_rect(),
+            self.frame_cap_rect(),
+            self.reset_stats_rect(),
+            self.settings_close_rect(),
+        ];
+        if self.can_quit_to_menu() {
+            rects.push(self.quit_to_menu_rect());
+        }
+        rects
+    }
+
+    // Same order as settings_button_rects
+    fn activate_settings_button(&mut self, button: usize) {
+        // Any other button cancels a pending stats reset or quit
+        if button != 10 {
+            self.reset_stats_armed_at = None;
+        }
+        if button != 12 {
+            self.quit_to_menu_armed_at = None;
+        }
+
+        match button {
+            0 => self.settings.animations = !self.settings.animations,
+            // Applied on the next update, which has the context
+            1 => self.settings.layout = self.settings.layout.next(),
+            2 => self.settings.auto_upgrade = !self.settings.auto_upgrade,
+            3 => self.settings.cycle_auto_upgrade_reserve(),
+            4 => self.settings.cycle_gold_decimals(),
+            5 => self.settings.low_health_vignette = !self.settings.low_health_vignette,
+            6 => self.settings.activity_timestamps = !self.settings.activity_timestamps,
+            7 => self.settings.reveal_strategies = !self.settings.reveal_strategies,
+            8 => self.settings.show_activity_log = !self.settings.show_activity_log,
+            // Capped rates apply on the next frame; vsync waits for a restart
+            9 => self.settings.frame_cap = self.settings.frame_cap.next(),
+            10 => {
+                self.request_reset_stats();
+                return;
+            },
+            12 => {
+                self.request_quit_to_menu();
+                return;
+            },
+            _ => {
+                self.close_settings();
+                return;
+            }
+        }
+        self.settings.save();
+    }
+
+    // Keyboard order in the settings overlay: the volume sliders, then the buttons
+    fn settings_item_count(&self) -> usize {
+        VolumeChannel::ALL.len() + self.settings_button_rects().len()
+    }
+
+    // Up/Down move between settings, Left/Right nudge a volume slider, Enter presses a button
+    fn handle_settings_key(&mut self, keycode: KeyCode) {
+        let count = self.settings_item_count();
+        let sliders = VolumeChannel::ALL.len();
+
+        match keycode {
+            KeyCode::Up => self.settings_focus = (self.settings_focus + count - 1) % count,
+            KeyCode::Down => self.settings_focus = (self.settings_focus + 1) % count,
+            KeyCode::Left | KeyCode::Right if self.settings_focus < sliders => {
+                let channel = VolumeChannel::ALL[self.settings_focus];
+                let step = if keycode == KeyCode::Right { 0.1 } else { -0.1 };
+                self.settings.set_volume(channel, self.settings.volume(channel) + step);
+                self.audio.apply_volume(&self.settings);
+                self.settings.save();
+            },
+            KeyCode::Return | KeyCode::NumpadEnter if self.settings_focus >= sliders => {
+                self.activate_settings_button(self.settings_focus - sliders);
+            },
+            _ => return,
+        }
+
+        self.keyboard_focus_visible = true;
+    }
+
+    // Outline for the keyboard-selected settings item, while keyboard focus is showing
+    pub fn settings_focus_rect(&self) -> Option<Rect> {
+        if !self.keyboard_focus_visible {
+            return None;
+        }
+
+        let sliders = VolumeChannel::ALL.len();
+        if self.settings_focus < sliders {
+            Some(self.volume_slider_hit_rect(self.settings_focus))
+        } else {
+            self.settings_button_rects().get(self.settings_focus - sliders).copied()
+        }
+    }
+
+    // Sets the dragged slider's volume from the cursor's x position
+    pub fn drag_slider_to(&mut self, x: f32) {
+        if let Some(channel) = self.dragging_slider {
+            let index = VolumeChannel::ALL.iter().position(|c| *c == channel).unwrap_or(0);
+            let slider = self.volume_slider_rect(index);
+            let volume = (x - slider.x) / slider.w;
+
+            self.settings.set_volume(channel, volume);
+            self.audio.apply_volume(&self.settings);
+        }
+    }
+
+    // Puts `overlay` on top; one already open stays where it is
+    pub fn open_overlay(&mut self, overlay: Overlay) {
+        if !self.overlays.contains(&overlay) {
+            self.overlays.push(overlay);
+        }
+    }
+
+    pub fn close_overlay(&mut self, overlay: Overlay) {
+        self.overlays.retain(|open| *open != overlay);
+    }
+
+    // The overlay clicks go to, if any
+    pub fn top_overlay(&self) -> Option<Overlay> {
+        self.overlays.last().copied()
+    }
+
+    // Opens the detail popup for bots[bot_index], from a click on its opponents panel row
+    fn open_bot_detail(&mut self, bot_index: usize) {
+        self.selected_bot = Some(bot_index);
+        self.open_overlay(Overlay::BotDetail);
+    }
+
+    fn close_bot_detail(&mut self) {
+        self.close_overlay(Overlay::BotDetail);
+        self.selected_bot = None;
+    }
+
+    // The popup covers part of the screen; clicks outside it dismiss it
+    fn handle_bot_detail_click(&mut self, x: f32, y: f32) {
+        if !self.bot_detail_rect().contains([x, y]) {
+            self.close_bot_detail();
+        }
+    }
+
+    pub fn bot_detail_rect(&self) -> Rect {
+        let (center_x, center_y) = self.screen_center();
+        Rect::new(center_x - 190.0, center_y - 180.0, 380.0, 360.0)
+    }
+
+    // The bot whose opponents panel row is under (x, y). Rows follow opponent_display_order,
+    // so hidden dead bots and sorting are accounted for.
+    pub fn opponent_row_at(&self, x: f32, y: f32) -> Option<usize> {
+        self.opponent_display_order()
+            .into_iter()
+            .enumerate()
+            .find(|(row, _)| opponent_row_rect(*row).contains([x, y]))
+            .map(|(_, bot_index)| bot_index)
+    }
+
+    pub fn settings_open(&self) -> bool {
+        self.overlays.contains(&Overlay::Settings)
+    }
+
+    // Wiping the lifetime stats goes through on the second press; the first one only arms it
+    fn request_reset_stats(&mut self) {
+        if self.reset_stats_armed() {
+            self.reset_stats_armed_at = None;
+            self.lifetime_stats.reset();
+        } else {
+            self.reset_stats_armed_at = Some(Instant::now());
+        }
+    }
+
+    // Reset Stats was pressed once and is waiting for confirmation
+    pub fn reset_stats_armed(&self) -> bool {
+        self.reset_stats_armed_at.is_some_and(|time| time.elapsed() < RESET_STATS_CONFIRM_WINDOW)
+    }
+
+    // A run is in progress that Quit to Menu can abandon
+    pub fn can_quit_to_menu(&self) -> bool {
+        matches!(self.game_state, GameState::Playing | GameState::RoundEnd)
+    }
+
+    // Abandoning the run goes through on the second press; the first one only arms it
+    fn request_quit_to_menu(&mut self) {
+        if !self.can_quit_to_menu() {
+            return;
+        }
+
+        if self.quit_to_menu_armed() {
+            self.quit_to_menu();
+        } else {
+            self.quit_to_menu_armed_at = Some(Instant::now());
+        }
+    }
+
+    // Quit to Menu was pressed once and is waiting for confirmation
+    pub fn quit_to_menu_armed(&self) -> bool {
+        self.quit_to_menu_armed_at.is_some_and(|time| time.elapsed() < QUIT_TO_MENU_CONFIRM_WINDOW)
+    }
+
+    // Drops the current run and goes back to the start menu. Unlike finishing a game this
+    // doesn't touch the lifetime stats, so an abandoned run isn't counted as a loss - except
+    // in hardcore, where quitting can't be a way out of a run that's going badly.
+    fn quit_to_menu(&mut self) {
+        if self.config.hardcore && self.counts_for_stats() {
+            self.lifetime_stats.record_game(false, true);
+        }
+
+        self.close_settings();
+        self.overlays.clear();
+        self.show_menu();
+        self.round_results = None;
+        self.settings_focus = 0; // the Quit to Menu button it was on is gone
+
+        // Nothing left to continue
+        save::delete_save();
+        self.save_available = false;
+    }
+
+    pub fn close_settings(&mut self) {
+        self.close_overlay(Overlay::Settings);
+        self.reset_stats_armed_at = None;
+        self.quit_to_menu_armed_at = None;
+        self.dragging_slider = None;
+        self.settings.save();
+    }
+
+    pub fn handle_game_over_ui_click(&mut self, x: f32, y: f32) {
+        // Check restart button
+        if !self.restart_locked() && self.game_over_restart_rect().contains([x, y]) {
+            // Same settings, fresh run
+            self.restart_game();
+        }
+
+        // Check change settings button
+        if self.game_over_settings_rect().contains([x, y]) {
+            self.show_menu();
+        }
+
+        // Keep this game's seed for the next one (Restart, R, or Start Game on the menu)
+        if self.game_over_seed_rect().contains([x, y]) {
+            self.seed_input = self.seed.to_string();
+        }
+    }
+
+    // Everything the click handlers for the current screen respond to
+    fn clickable_rects(&self) -> Vec<Rect> {
+        if self.draw_error.is_some() {
+            return vec![self.error_continue_rect(), self.error_quit_rect()];
+        }
+
+        // Nothing under the comparison table or bot popup can be clicked
+        if matches!(self.top_overlay(), Some(Overlay::Comparison) | Some(Overlay::BotDetail)) {
+            return Vec::new();
+        }
+
+        if self.settings_open() {
+            let mut rects: Vec<Rect> = (0..VolumeChannel::ALL.len())
+                .map(|i| self.volume_slider_hit_rect(i))
+                .collect();
+            rects.extend(self.settings_button_rects());
+            return rects;
+        }
+
+        match self.game_state {
+            GameState::Menu => {
+                let mut rects = Vec::new();
+                for option in 0..MENU_OPTION_COUNT {
+                    let (left_rect, right_rect) = self.menu_arrow_rects(option);
+                    rects.push(left_rect);
+                    rects.push(right_rect);
+                }
+                rects.push(self.menu_seed_rect());
+                rects.push(self.menu_quick_match_rect());
+                rects.push(self.menu_start_rect());
+                if self.save_available {
+                    rects.push(self.menu_continue_rect());
+                }
+                rects
+            },
+            GameState::Playing => {
+                let mut rects = vec![settings_button_rect(), dead_bots_toggle_rect()];
+                rects.extend((0..UpgradeKind::ALL.len()).map(upgrade_button_rect));
+                rects.extend((0..self.opponent_display_order().len()).map(opponent_row_rect));
+                if self.config.turn_based {
+                    rects.push(end_turn_button_rect());
+                }
+                rects.extend((0..OpponentSort::ALL.len()).map(opponent_sort_rect));
+                rects.extend((0..=DONATION_TIERS.len()).map(donation_button_rect));
+                if self.pets.len() < MAX_PETS && self.player.gold >= self.pet_unlock_cost() {
+                    rects.push(pet_unlock_button_rect(self.pets.len()));
+                }
+                for (index, pet) in self.pets.iter().enumerate() {
+                    if pet.alive {
+                        rects.extend((0..PetColor::ALL.len()).map(|i| pet_swatch_rect(index, i)));
+                        rects.push(pet_mine_button_rect(index));
+                        rects.push(pet_search_button_rect(index));
+                    } else if self.can_revive_pet(index) {
+                        rects.push(pet_revive_button_rect(index));
+                    }
+                }
+                rects
+            },
+            GameState::RoundEnd => {
+                let mut rects = vec![self.round_end_continue_rect()];
+                if self.undoable_upgrade().is_some() {
+                    rects.push(self.round_end_undo_rect());
+                }
+                if self.round_results.is_some() {
+                    rects.push(self.round_end_keep_rect());
+                    if self.can_cash_in() {
+                        rects.push(self.round_end_cash_in_rect());
+                    }
+                }
+                rects
+            },
+            GameState::GameOver if self.restart_locked() => vec![self.game_over_settings_rect(), self.game_over_seed_rect()],
+            GameState::GameOver => vec![self.game_over_restart_rect(), self.game_over_settings_rect(), self.game_over_seed_rect()],
+        }
+    }
+
+    // Pixels per layout unit for the chosen layout preset
+    fn target_ui_scale(&self) -> f32 {
+        let fits = |scale: f32| {
+            self.window_size.0 >= GAMEPLAY_WIDTH * scale && self.window_size.1 >= WINDOW_HEIGHT * scale
+        };
+
+        match self.settings.layout {
+            LayoutMode::Compact => COMPACT_SCALE,
+            LayoutMode::Spacious => SPACIOUS_SCALE,
+            LayoutMode::Auto if fits(SPACIOUS_SCALE) => SPACIOUS_SCALE,
+            LayoutMode::Auto if fits(1.0) => 1.0,
+            LayoutMode::Auto => COMPACT_SCALE,
+        }
+    }
+
+    // Maps the window onto layout units for the active preset. Everything (drawing, click
+    // rects, screen_center) works in layout units, so only mouse positions need converting.
+    fn apply_layout(&mut self, ctx: &mut Context) {
+        self.update_layout();
+
+        let screen_rect = Rect::new(0.0, 0.0, self.screen_size.0, self.screen_size.1);
+        if let Err(e) = graphics::set_screen_coordinates(ctx, screen_rect) {
+            eprintln!("Could not update screen coordinates: {}", e);
+        }
+    }
+
+    fn update_layout(&mut self) {
+        self.ui_scale = self.target_ui_scale();
+        self.screen_size = (self.window_size.0 / self.ui_scale, self.window_size.1 / self.ui_scale);
+    }
+
+    // A mouse position in window pixels, in layout units
+    fn to_layout(&self, x: f32, y: f32) -> (f32, f32) {
+        (x / self.ui_scale, y / self.ui_scale)
+    }
+
+    // Hand cursor over anything clickable, the normal arrow everywhere else
+    fn update_cursor_icon(&mut self, ctx: &mut Context) {
+        let (x, y) = self.cursor_position;
+        let over_clickable = self.clickable_rects().iter().any(|rect| rect.contains([x, y]));
+
+        if over_clickable != self.pointer_cursor {
+            self.pointer_cursor = over_clickable;
+            mouse::set_cursor_type(ctx, if over_clickable { CursorIcon::Hand } else { CursorIcon::Default });
+        }
+    }
+
+    pub fn handle_menu_click(&mut self, x: f32, y: f32) {
+        for option in 0..MENU_OPTION_COUNT {
+            let (left_rect, right_rect) = self.menu_arrow_rects(option);
+
+            if left_rect.contains([x, y]) {
+                self.adjust_menu_option(option, false);
+            } else if right_rect.contains([x, y]) {
+                self.adjust_menu_option(option, true);
+            }
+        }
+
+        // Clicking the seed field focuses it, clicking anywhere else drops the focus
+        self.seed_input_focused = self.menu_seed_rect().contains([x, y]);
+
+        if self.menu_quick_match_rect().contains([x, y]) {
+            self.quick_match();
+        } else if self.menu_start_rect().contains([x, y]) {
+            self.restart_game();
+        } else if self.save_available && self.menu_continue_rect().contains([x, y]) {
+            self.continue_saved_game();
+        }
+    }
+
+    // Starts a game on the default options (Normal, default bots and rounds, random seed),
+    // whatever is picked on the menu
+    fn quick_match(&mut self) {
+        self.menu_config = Some(self.config);
+        self.config = GameConfig::default();
+        self.seed_input.clear();
+        self.restart_game();
+    }
+
+    // Back to the start menu, showing the options picked there even after a quick match
+    fn show_menu(&mut self) {
+        self.game_state = GameState::Menu;
+        if let Some(config) = self.menu_config.take() {
+            self.config = config;
+        }
+    }
+
+    // Keyboard order on the menu: the option rows, the seed field, Quick Match, Start,
+    // then Continue if there's a save
+    fn menu_item_count(&self) -> usize {
+        MENU_OPTION_COUNT + 3 + if self.save_available { 1 } else { 0 }
+    }
+
+    // Up/Down move between menu items, Left/Right change an option, Enter picks the item
+    fn handle_menu_key(&mut self, keycode: KeyCode) {
+        let count = self.menu_item_count();
+        self.menu_focus = self.menu_focus.min(count - 1);
+
+        match keycode {
+            KeyCode::Up => self.menu_focus = (self.menu_focus + count - 1) % count,
+            KeyCode::Down => self.menu_focus = (self.menu_focus + 1) % count,
+            KeyCode::Left | KeyCode::Right if self.menu_focus < MENU_OPTION_COUNT => {
+                self.adjust_menu_option(self.menu_focus, keycode == KeyCode::Right);
+            },
+            KeyCode::Return | KeyCode::NumpadEnter => {
+                if self.menu_focus < MENU_OPTION_COUNT {
+                    self.adjust_menu_option(self.menu_focus, true);
+                } else if self.menu_focus == MENU_OPTION_COUNT {
+                    // Enter starts and stops typing into the seed field
+                    self.seed_input_focused = !self.seed_input_focused;
+                } else if self.menu_focus == MENU_QUICK_MATCH {
+                    self.quick_match();
+                } else if self.menu_focus == MENU_QUICK_MATCH + 1 {
+                    self.restart_game();
+                } else {
+                    self.continue_saved_game();
+                }
+            },
+            _ => return,
+        }
+
+        // Moving off the seed field stops typing into it
+        if self.menu_focus != MENU_OPTION_COUNT {
+            self.seed_input_focused = false;
+        }
+        self.keyboard_focus_visible = true;
+    }
+
+    // Outline for the keyboard-selected menu item, while keyboard focus is showing
+    pub fn menu_focus_rect(&self) -> Option<Rect> {
+        if !self.keyboard_focus_visible {
+            return None;
+        }
+
+        let focus = self.menu_focus.min(self.menu_item_count() - 1);
+        let rect = if focus < MENU_OPTION_COUNT {
+            // The whole row, label to right arrow
+            Rect::new(self.menu_option_x(focus) - 8.0, self.menu_option_y(focus) - 4.0, MENU_COLUMN_WIDTH + 16.0, 44.0)
+        } else if focus == MENU_OPTION_COUNT {
+            self.menu_seed_rect()
+        } else if focus == MENU_QUICK_MATCH {
+            self.menu_quick_match_rect()
+        } else if focus == MENU_QUICK_MATCH + 1 {
+            self.menu_start_rect()
+        } else {
+            self.menu_continue_rect()
+        };
+
+        Some(rect)
+    }
+
+    // Menu rows, in order: bot count, difficulty, round length, damage model, donation lock,
+    // adaptive difficulty, first round shield, free pet, practice mode, turn-based
+    pub fn adjust_menu_option(&mut self, option: usize, forward: bool) {
+        match option {
+            0 => self.config.change_bot_count(if forward { 1 } else { -1 }),
+            1 => {
+                let difficulty = if forward {
+                    self.config.difficulty.next()
+                } else {
+                    self.config.difficulty.previous()
+                };
+                self.config.set_difficulty(difficulty);
+            },
+            2 => self.config.cycle_round_length(forward),
+            3 => {
+                self.config.damage_model = if forward {
+                    self.config.damage_model.next()
+                } else {
+                    self.config.damage_model.previous()
+                };
+            },
+            4 => self.config.cycle_donation_lock(forward),
+            5 => self.config.adaptive_difficulty = !self.config.adaptive_difficulty,
+            6 => self.config.first_round_shield = !self.config.first_round_shield,
+            7 => self.config.free_pet = !self.config.free_pet,
+            8 => self.config.sandbox = !self.config.sandbox,
+            9 => self.config.turn_based = !self.config.turn_based,
+            10 => self.config.bot_forecast = !self.config.bot_forecast,
+            11 => self.config.hardcore = !self.config.hardcore,
+            12 => self.config.cycle_starting_health(forward),
+            13 => self.config.cycle_yield_curve(forward),
+            _ => {}
+        }
+    }
+
+    // The current screen plus any overlay. Errors go back to draw(), which shows the error screen.
+    fn draw_screen(&mut self, ctx: &mut Context) -> GameResult {
+        // Draw UI based on game state
+        match self.game_state {
+            GameState::Menu => {
+                ui::draw_menu_ui(self, ctx)?;
+            },
+            GameState::Playing => {
+                ui::draw_game_ui(self, ctx)?;
+
+                if self.show_standings_preview {
+                    ui::draw_standings_preview(self, ctx)?;
+                }
+
+                // Drawn over everything else, but the round keeps running underneath
+                if self.overlays.contains(&Overlay::Comparison) {
+                    ui::draw_comparison_table(self, ctx)?;
+                }
+                if self.overlays.contains(&Overlay::BotDetail) {
+                    ui::draw_bot_detail(self, ctx)?;
+                }
+            },
+            GameState::RoundEnd => {
+                ui::draw_round_end_ui(self, ctx)?;
+            },
+            // Winning gets its own celebration rather than a recolored game over screen
+            GameState::GameOver if self.player_has_won() => {
+                ui::draw_victory_ui(self, ctx)?;
+            },
+            GameState::GameOver => {
+                ui::draw_defeat_ui(self, ctx)?;
+            },
+        }
+
+        // Settings overlay sits on top of whichever screen is showing
+        if self.settings_open() {
+            ui::draw_settings_overlay(self, ctx)?;
+        }
+
+        Ok(())
+    }
+}
+
+// True when every miner in a round's results donated nothing
+pub fn nobody_donated(results: &[(usize, f32)]) -> bool {
+    results.iter().all(|(_, donated)| *donated <= 0.0)
+}
+
+// The Balanced bots' upgrade pick, also used by the player's auto-upgrade. Never spends
+// below `reserve` gold.
+fn balanced_upgrade(miner: &mut Miner, rng: &mut ChaCha12Rng, reserve: f32) {
+    let spendable = miner.gold - reserve;
+    let lowest = UpgradeKind::ALL.iter().map(|&kind| miner.level(kind)).min().unwrap_or(0);
+    let levels_even = UpgradeKind::ALL.iter().all(|&kind| miner.level(kind) == lowest);
+
+    // Prioritize whichever upgrade is behind, to catch up
+    let behind = UpgradeKind::ALL
+        .into_iter()
+        .find(|&kind| miner.level(kind) == lowest && miner.can_upgrade(kind, spendable));
+
+    match behind {
+        Some(kind) if !levels_even => {
+            miner.upgrade(kind);
+        },
+        _ => {
+            // If levels are equal, decide randomly which to upgrade
+            let kind = UpgradeKind::ALL[rng.gen_range(0..UpgradeKind::ALL.len())];
+            if miner.can_upgrade(kind, spendable) {
+                miner.upgrade(kind);
+            }
+        }
+    }
+}
+
+// Creates the bot line-up, one strategy per slot
+fn spawn_bots(config: &GameConfig) -> Vec<Miner> {
+    (0..config.bot_count)
+        .map(|i| Miner {
+            max_level: config.upgrade_cap(),
+            health: config.starting_health,
+            max_health: config.starting_health,
+            yield_curve: config.yield_curve,
+            ..Miner::new_bot(BotStrategy::for_index(i))
+        })
+        .collect()
+}
+
+fn spawn_player(config: &GameConfig) -> Miner {
+    Miner {
+        max_level: config.upgrade_cap(),
+        health: config.starting_health,
+        max_health: config.starting_health,
+        yield_curve: config.yield_curve,
+        ..Miner::new(MinerType::Player)
+    }
+}
+
+// Start menu layout. The options fill two columns, so the whole menu fits in the
+// smallest window (WINDOW_WIDTH x WINDOW_HEIGHT layout units).
+pub const MENU_OPTION_COUNT: usize = 14;
+const MENU_ROWS: usize = MENU_OPTION_COUNT.div_ceil(2);
+const MENU_COLUMN_WIDTH: f32 = 360.0; // label, then the arrows and value
+const MENU_ROW_PITCH: f32 = 46.0;
+
+// Keyboard index of the Quick Match button, which has the focus until the player moves it
+const MENU_QUICK_MATCH: usize = MENU_OPTION_COUNT + 1;
+
+// Settings overlay layout. The option buttons fill two columns of rows, so adding one
+// doesn't make the panel taller than the smallest window.
+const SETTINGS_ROWS_PER_COLUMN: usize = 5;
+pub const SETTINGS_LABEL_WIDTH: f32 = 170.0; // room for an option's label, left of its button
+
+// Gameplay header button; the gameplay panels keep their fixed layout
+pub fn settings_button_rect() -> Rect {
+    Rect::new(WINDOW_WIDTH - 130.0, 24.0, 110.0, 32.0)
+}
+
+// Takes the round timer's place in the header in turn-based games
+pub fn end_turn_button_rect() -> Rect {
+    Rect::new(200.0, 20.0, 300.0, 40.0)
+}
+
+// Gameplay button for upgrade `index` in UpgradeKind::ALL, matching draw_upgrade_options
+pub fn upgrade_button_rect(index: usize) -> Rect {
+    Rect::new(30.0, 220.0 + index as f32 * 50.0, 200.0, 40.0)
+}
+
+// Donation button `index` in the contribute panel: the DONATION_TIERS in order, then "All"
+pub fn donation_button_rect(index: usize) -> Rect {
+    Rect::new(WINDOW_WIDTH - 240.0, 190.0 + (index as f32 * 40.0), 220.0, 30.0)
+}
+
+// Sort header `index` (matching OpponentSort::ALL) in the opponents panel header row
+pub fn opponent_sort_rect(index: usize) -> Rect {
+    Rect::new(160.0 + index as f32 * 80.0, 338.0, 74.0, 26.0)
+}
+
+pub fn dead_bots_toggle_rect() -> Rect {
+    Rect::new(410.0, 338.0, 120.0, 26.0)
+}
+
+// Row `row` of the opponents panel, counting down in display order
+pub fn opponent_row_rect(row: usize) -> Rect {
+    Rect::new(20.0, 375.0 + row as f32 * 50.0, WINDOW_WIDTH - 300.0, 40.0)
+}
+
+// Top of the pet panel slot for owned pet `index`
+pub fn pet_slot_y(index: usize) -> f32 {
+    60.0 + index as f32 * 135.0
+}
+
+// Unlock button when `owned` pets are already owned: under the lock info with none,
+// otherwise in the next free slot
+pub fn pet_unlock_button_rect(owned: usize) -> Rect {
+    if owned == 0 {
+        Rect::new(815.0, 260.0, 220.0, 40.0)
+    } else {
+        Rect::new(815.0, pet_slot_y(owned) + 5.0, 220.0, 40.0)
+    }
+}
+
+// Pet panel buttons
+pub fn pet_mine_button_rect(index: usize) -> Rect {
+    Rect::new(815.0, pet_slot_y(index) + 45.0, 105.0, 34.0)
+}
+
+pub fn pet_search_button_rect(index: usize) -> Rect {
+    Rect::new(930.0, pet_slot_y(index) + 45.0, 105.0, 34.0)
+}
+
+// Under the dead pet's message, in place of its swatches
+pub fn pet_revive_button_rect(index: usize) -> Rect {
+    Rect::new(815.0, pet_slot_y(index) + 92.0, 220.0, 32.0)
+}
+
+// Color swatch `index` in pet `pet_index`'s slot, matching draw_pet_interface
+pub fn pet_swatch_rect(pet_index: usize, index: usize) -> Rect {
+    Rect::new(815.0 + index as f32 * 40.0, pet_slot_y(pet_index) + 90.0, 30.0, 30.0)
+}
+
+// Screens and overlays centered on the current window size, shared by the click handlers and ui.rs
+impl MainState {
+    pub fn screen_center(&self) -> (f32, f32) {
+        (self.screen_size.0 / 2.0, self.screen_size.1 / 2.0)
+    }
+
+    pub fn menu_panel_rect(&self) -> Rect {
+        let (center_x, center_y) = self.screen_center();
+        Rect::new(center_x - 390.0, (center_y - 295.0).max(5.0), 780.0, 590.0)
+    }
+
+    // Left edge of an option's column: the first MENU_ROWS options on the left, the rest on the right
+    pub fn menu_option_x(&self, option: usize) -> f32 {
+        let column = if option < MENU_ROWS { 0.0 } else { 1.0 };
+        self.menu_panel_rect().x + 20.0 + column * (MENU_COLUMN_WIDTH + 20.0)
+    }
+
+    pub fn menu_option_y(&self, option: usize) -> f32 {
+        self.menu_panel_rect().y + 160.0 + ((option % MENU_ROWS) as f32 * MENU_ROW_PITCH)
+    }
+
+    pub fn menu_arrow_rects(&self, option: usize) -> (Rect, Rect) {
+        let x = self.menu_option_x(option);
+        let y = self.menu_option_y(option);
+        (
+            Rect::new(x + 150.0, y, 40.0, 36.0),
+            Rect::new(x + MENU_COLUMN_WIDTH - 40.0, y, 40.0, 36.0),
+        )
+    }
+
+    // Seed entry, in the left column's row after the last option
+    pub fn menu_seed_rect(&self) -> Rect {
+        let x = self.menu_option_x(0);
+        let y = self.menu_panel_rect().y + 160.0 + MENU_ROWS as f32 * MENU_ROW_PITCH;
+        Rect::new(x + 150.0, y, MENU_COLUMN_WIDTH - 150.0, 36.0)
+    }
+
+    // Quick Match and Start Game side by side at the bottom, with Continue to their left
+    // when there's a save
+    pub fn menu_quick_match_rect(&self) -> Rect {
+        self.menu_bottom_button_rect(if self.save_available { -100.0 } else { -210.0 })
+    }
 
-                let gold_earned_this_update = self.player.gold - previous_gold;
-                if gold_earned_this_update > 0.0 {
-                    self.total_gold_earned += gold_earned_this_update;
-                }
-                
-                // Make random decisions for bots
-                for i in 0..self.bots.len() {
-                    self.bot_make_decision(i);
-                }
+    pub fn menu_start_rect(&self) -> Rect {
+        self.menu_bottom_button_rect(if self.save_available { 120.0 } else { 10.0 })
+    }
 
-                // Check if round is over
-                let now = Instant::now();
-                let round_elapsed = now.duration_since(self.round_start_time);
-                if round_elapsed >= ROUND_DURATION {
-                    self.end_round();
-                }
+    pub fn menu_continue_rect(&self) -> Rect {
+        self.menu_bottom_button_rect(-320.0)
+    }
+
+    // A button in the menu's bottom row, `offset` from the panel's center line
+    fn menu_bottom_button_rect(&self, offset: f32) -> Rect {
+        let panel = self.menu_panel_rect();
+        Rect::new(panel.x + panel.w / 2.0 + offset, panel.y + panel.h - 62.0, 200.0, 46.0)
+    }
+
+    pub fn settings_panel_rect(&self) -> Rect {
+        let (center_x, center_y) = self.screen_center();
+        Rect::new(center_x - 360.0, center_y - 265.0, 720.0, 530.0)
+    }
+
+    pub fn volume_slider_rect(&self, index: usize) -> Rect {
+        let panel = self.settings_panel_rect();
+        Rect::new(panel.x + 160.0, panel.y + 85.0 + (index as f32 * 50.0), 200.0, 14.0)
+    }
+
+    // Slider plus a more forgiving hit area around the thin bar
+    pub fn volume_slider_hit_rect(&self, index: usize) -> Rect {
+        let slider = self.volume_slider_rect(index);
+        Rect::new(slider.x - 8.0, slider.y - 10.0, slider.w + 16.0, slider.h + 20.0)
+    }
+
+    // Height of the round end panel. Without results the UI draws an empty
+    // "Loading results..." panel (no rows, no gold choice), and its Continue still has to work.
+    // An undo offer adds a row above the gold choice.
+    pub fn round_end_panel_height(&self) -> f32 {
+        let undo_row = if self.undoable_upgrade().is_some() { 50.0 } else { 0.0 };
+        match &self.round_results {
+            Some(results) => (results.len() as f32 * 40.0) + 200.0 + undo_row,
+            None => 150.0,
+        }
+    }
+
+    // Damage cell of the results row at `position`
+    pub fn round_end_damage_rect(&self, position: usize) -> Rect {
+        let panel_height = self.round_end_panel_height();
+        let (center_x, center_y) = self.screen_center();
+        let panel_y = center_y - panel_height / 2.0;
+
+        Rect::new(center_x - 250.0 + 360.0, panel_y + 95.0 + position as f32 * 40.0, 60.0, 30.0)
+    }
+
+    // Continue button under the results
+    pub fn round_end_continue_rect(&self) -> Rect {
+        let panel_height = self.round_end_panel_height();
+        let (center_x, center_y) = self.screen_center();
+        let panel_y = center_y - panel_height / 2.0;
+
+        Rect::new(center_x - 125.0, panel_y + panel_height - 60.0, 250.0, 40.0)
+    }
+
+    // Gold choice buttons, side by side above Continue
+    pub fn round_end_keep_rect(&self) -> Rect {
+        let continue_rect = self.round_end_continue_rect();
+        let (center_x, _) = self.screen_center();
+        Rect::new(center_x - 210.0, continue_rect.y - 50.0, 200.0, 36.0)
+    }
+
+    pub fn round_end_cash_in_rect(&self) -> Rect {
+        let continue_rect = self.round_end_continue_rect();
+        let (center_x, _) = self.screen_center();
+        Rect::new(center_x + 10.0, continue_rect.y - 50.0, 200.0, 36.0)
+    }
+
+    // Undo offer for a last-second upgrade, above the gold choice
+    pub fn round_end_undo_rect(&self) -> Rect {
+        let continue_rect = self.round_end_continue_rect();
+        let (center_x, _) = self.screen_center();
+        Rect::new(center_x - 210.0, continue_rect.y - 100.0, 420.0, 36.0)
+    }
+
+    // Summary panel of either end screen; its buttons are placed relative to it
+    pub fn game_over_panel_rect(&self) -> Rect {
+        let (center_x, center_y) = self.screen_center();
+        Rect::new(center_x - 250.0, center_y - 240.0, 500.0, 520.0)
+    }
+
+    pub fn game_over_restart_rect(&self) -> Rect {
+        let panel = self.game_over_panel_rect();
+        Rect::new(panel.x + panel.w / 2.0 - 160.0, panel.y + 400.0, 150.0, 40.0)
+    }
+
+    // Centered on its own when there's no Restart button beside it
+    pub fn game_over_settings_rect(&self) -> Rect {
+        let panel = self.game_over_panel_rect();
+        let offset = if self.restart_locked() { -75.0 } else { 10.0 };
+        Rect::new(panel.x + panel.w / 2.0 + offset, panel.y + 400.0, 150.0, 40.0)
+    }
+
+    pub fn game_over_seed_rect(&self) -> Rect {
+        let panel = self.game_over_panel_rect();
+        Rect::new(panel.x + panel.w / 2.0 + 70.0, panel.y + 450.0, 150.0, 30.0)
+    }
+
+    // Button for settings row `row`, counting down the left column under the volume sliders
+    // and then the right one. Its label sits SETTINGS_LABEL_WIDTH to the left.
+    fn settings_row_rect(&self, row: usize) -> Rect {
+        let panel = self.settings_panel_rect();
+        let column = (row / SETTINGS_ROWS_PER_COLUMN) as f32;
+        let row = (row % SETTINGS_ROWS_PER_COLUMN) as f32;
+        Rect::new(panel.x + 20.0 + SETTINGS_LABEL_WIDTH + column * 360.0, panel.y + 225.0 + row * 36.0, 150.0, 30.0)
+    }
+
+    pub fn animations_toggle_rect(&self) -> Rect {
+        self.settings_row_rect(0)
+    }
+
+    pub fn layout_toggle_rect(&self) -> Rect {
+        self.settings_row_rect(1)
+    }
+
+    pub fn auto_upgrade_toggle_rect(&self) -> Rect {
+        self.settings_row_rect(2)
+    }
+
+    pub fn auto_upgrade_reserve_rect(&self) -> Rect {
+        self.settings_row_rect(3)
+    }
+
+    pub fn gold_decimals_rect(&self) -> Rect {
+        self.settings_row_rect(4)
+    }
+
+    pub fn low_health_vignette_rect(&self) -> Rect {
+        self.settings_row_rect(5)
+    }
+
+    pub fn activity_timestamps_rect(&self) -> Rect {
+        self.settings_row_rect(6)
+    }
+
+    pub fn reveal_strategies_rect(&self) -> Rect {
+        self.settings_row_rect(7)
+    }
+
+    pub fn activity_log_toggle_rect(&self) -> Rect {
+        self.settings_row_rect(8)
+    }
+
+    pub fn frame_cap_rect(&self) -> Rect {
+        self.settings_row_rect(9)
+    }
+
+    // The chosen frame cap turns vsync on or off, which only happens when the window is created
+    pub fn frame_cap_needs_restart(&self) -> bool {
+        self.settings.frame_cap.vsync() != self.launch_vsync
+    }
+
+    // Holds the frame back to the chosen cap. Logic steps are counted from real time in
+    // update, so a lower cap just means more of them per frame.
+    fn pace_frame(&mut self) {
+        if let Some(interval) = self.settings.frame_cap.frame_interval() {
+            let spent = self.last_frame_at.elapsed();
+            if spent < interval {
+                timer::sleep(interval - spent);
+            }
+        }
+        self.last_frame_at = Instant::now();
+    }
+
+    pub fn error_panel_rect(&self) -> Rect {
+        let (center_x, center_y) = self.screen_center();
+        Rect::new(center_x - 250.0, center_y - 110.0, 500.0, 220.0)
+    }
+
+    pub fn error_continue_rect(&self) -> Rect {
+        let panel = self.error_panel_rect();
+        Rect::new(panel.x + panel.w / 2.0 - 160.0, panel.y + panel.h - 60.0, 150.0, 40.0)
+    }
+
+    pub fn error_quit_rect(&self) -> Rect {
+        let panel = self.error_panel_rect();
+        Rect::new(panel.x + panel.w / 2.0 + 10.0, panel.y + panel.h - 60.0, 150.0, 40.0)
+    }
+
+    // Reset Stats and Close share the bottom row
+    pub fn reset_stats_rect(&self) -> Rect {
+        let panel = self.settings_panel_rect();
+        Rect::new(panel.x + panel.w / 2.0 - 160.0, panel.y + panel.h - 60.0, 150.0, 40.0)
+    }
+
+    pub fn settings_close_rect(&self) -> Rect {
+        let panel = self.settings_panel_rect();
+        Rect::new(panel.x + panel.w / 2.0 + 10.0, panel.y + panel.h - 60.0, 150.0, 40.0)
+    }
+
+    // Above the bottom row, only drawn during a game
+    pub fn quit_to_menu_rect(&self) -> Rect {
+        let panel = self.settings_panel_rect();
+        Rect::new(panel.x + panel.w / 2.0 - 160.0, panel.y + panel.h - 110.0, 320.0, 40.0)
+    }
+}
+
+impl EventHandler for MainState {
+    fn update(&mut self, ctx: &mut Context) -> GameResult {
+        self.audio.play_queued(ctx, &self.settings);
+        self.update_cursor_icon(ctx);
+
+        // Picks up layout changes from the settings overlay
+        if self.target_ui_scale() != self.ui_scale {
+            self.apply_layout(ctx);
+        }
+
+        self.sync_round_timer();
+
+        // Logic steps due since the last frame. Always drained, so time spent on the menu
+        // or paused doesn't come back as a burst of steps.
+        let mut ticks = 0;
+        while timer::check_update_time(ctx, LOGIC_TICKS_PER_SECOND) {
+            ticks += 1;
+        }
+
+        match self.game_state {
+            GameState::Menu => {
+                // Nothing runs until a game is started
+            },
+            GameState::Playing if self.round_timer_paused() => {
+                // Overlay open mid-round - everything waits with the clock
+            },
+            GameState::Playing if self.config.turn_based => {
+                // Nothing happens between turns; the round moves on when the player ends the turn
+                self.ease_displayed_health(ticks.min(MAX_TICKS_PER_FRAME) as f32 * LOGIC_STEP.as_secs_f32());
+            },
+            GameState::Playing => {
+                // Game logic runs in fixed steps, so bots and the economy go at the same speed at any frame rate
+                self.run_logic_ticks(ticks);
             },
             GameState::RoundEnd => {
                 // Wait for player to continue - no updates to miners
@@ -591,37 +3181,134 @@ impl EventHandler for MainState {
         _ctx: &mut Context,
         keycode: KeyCode,
         keymods: KeyMods,
-        _repeat: bool,
+        repeat: bool,
     ) {
+        // Enter can also start a game from the menu or press Close in settings; that same
+        // press mustn't go on to end a turn
+        let enter_ends_turn = matches!(self.game_state, GameState::Playing) && !self.settings_open();
+
+        // Arrow keys and Enter drive the settings overlay and the start menu without a mouse
+        if self.settings_open() {
+            self.handle_settings_key(keycode);
+        } else if let GameState::Menu = self.game_state {
+            self.handle_menu_key(keycode);
+        }
+
+        // R restarts straight away from the game over screen, same as the Restart button
+        // (menu options are kept). Held keys don't chain into further restarts.
+        if let GameState::GameOver = self.game_state
+            && keycode == KeyCode::R
+            && !repeat
+            && !self.settings_open()
+            && !self.restart_locked()
+        {
+            self.restart_game();
+            return;
+        }
+
         // Only process cheatcodes during gameplay
         if let GameState::Playing = self.game_state {
             // Cheatcode 1: Shift+X for 1000 gold
-            if keycode == KeyCode::X && keymods.contains(KeyMods::SHIFT) {
+            if self.cheats_enabled && keycode == KeyCode::X && keymods.contains(KeyMods::SHIFT) {
                 // Add 1000 gold to player
                 self.player.gold += 1000.0;
+                self.flash_cheat("+1000 gold");
             }
             
             // Cheatcode 2: Shift+Y to skip 10 seconds
-            if keycode == KeyCode::Y && keymods.contains(KeyMods::SHIFT) {
+            if self.cheats_enabled && keycode == KeyCode::Y && keymods.contains(KeyMods::SHIFT) {
                 // Adjust the round_start_time to be 10 seconds earlier
                 // This makes the game think 10 more seconds have passed
                 if let Some(new_time) = self.round_start_time.checked_sub(std::time::Duration::from_secs(10)) {
                     self.round_start_time = new_time;
+                    self.flash_cheat("Time skipped (10s)");
                     
                     // If we would skip past the round end, just end the round
-                    let now = std::time::Instant::now();
-                    let round_elapsed = now.duration_since(self.round_start_time);
-                    if round_elapsed >= ROUND_DURATION {
+                    if self.round_elapsed() >= self.config.round_duration {
                         self.end_round();
                     }
                 }
             }
+
+            // Number keys (top row or keypad) donate: 1-5 for the fixed tiers, 0 for everything
+            let tier = match keycode {
+                KeyCode::Key1 | KeyCode::Numpad1 => Some(0),
+                KeyCode::Key2 | KeyCode::Numpad2 => Some(1),
+                KeyCode::Key3 | KeyCode::Numpad3 => Some(2),
+                KeyCode::Key4 | KeyCode::Numpad4 => Some(3),
+                KeyCode::Key5 | KeyCode::Numpad5 => Some(4),
+                _ => None,
+            };
+
+            if !repeat && !self.settings_open() {
+                if let Some(tier) = tier {
+                    self.donate(DONATION_TIERS[tier]);
+                } else if keycode == KeyCode::Key0 || keycode == KeyCode::Numpad0 {
+                    self.request_donate_all();
+                }
+            }
+
+            // Debug: Shift+M maxes both player upgrades for free (cheat builds only)
+            if self.cheats_enabled && keycode == KeyCode::M && keymods.contains(KeyMods::SHIFT) {
+                self.player.force_max_upgrades();
+                self.activity_log.push_miner(0, ActivityKind::Player, "Debug: upgrades maxed (cheat)".to_string(), self.round_elapsed());
+                self.flash_cheat("Upgrades maxed");
+            }
+
+            // Debug: Shift+B rewinds to the start of the round's final stretch (cheat builds only)
+            if self.cheats_enabled && keycode == KeyCode::B && keymods.contains(KeyMods::SHIFT) && !repeat {
+                self.rewind_final_stretch();
+            }
         }
         
+        // S toggles the projected standings overlay while playing, P switches donations
+        // between gold and share of the round's total, L hides or shows the activity log
+        if let GameState::Playing = self.game_state {
+            if keycode == KeyCode::S && !repeat {
+                self.show_standings_preview = !self.show_standings_preview;
+            }
+
+            if keycode == KeyCode::P && !repeat {
+                self.show_donation_share = !self.show_donation_share;
+            }
+
+            if keycode == KeyCode::L && !repeat {
+                self.settings.show_activity_log = !self.settings.show_activity_log;
+                self.settings.save();
+            }
+
+            // Enter ends the turn in turn-based games, same as the End Turn button
+            if matches!(keycode, KeyCode::Return | KeyCode::NumpadEnter) && !repeat && enter_ends_turn {
+                self.end_turn();
+            }
+
+            // Tab shows the miner comparison table for as long as it's held
+            if keycode == KeyCode::Tab && !self.settings_open() {
+                self.open_overlay(Overlay::Comparison);
+            }
+        }
+
+        // Backspace edits the menu's seed field (digits come in through text_input_event)
+        if let GameState::Menu = self.game_state
+            && self.seed_input_focused
+            && keycode == KeyCode::Back
+        {
+            self.seed_input.pop();
+        }
+
         // Check for "-" key press (always active in any game state)
         if keycode == KeyCode::Minus {
             self.show_cursor_position = true;
         }
+
+        // Escape opens/closes the settings overlay in any game state
+        if keycode == KeyCode::Escape {
+            if self.settings_open() {
+                self.close_settings();
+            } else {
+                self.open_overlay(Overlay::Settings);
+            }
+        }
     }
 
     fn key_up_event(
@@ -634,6 +3321,24 @@ impl EventHandler for MainState {
         if keycode == KeyCode::Minus {
             self.show_cursor_position = false;
         }
+
+        if keycode == KeyCode::Tab {
+            self.close_overlay(Overlay::Comparison);
+        }
+    }
+
+    fn text_input_event(&mut self, _ctx: &mut Context, character: char) {
+        // Only digits go into the seed field, and only while it still fits in a u64,
+        // so a typed seed is always the one that gets played
+        if let GameState::Menu = self.game_state
+            && self.seed_input_focused
+            && character.is_ascii_digit()
+        {
+            self.seed_input.push(character);
+            if self.seed_input.parse::<u64>().is_err() {
+                self.seed_input.pop();
+            }
+        }
     }
 
     fn mouse_motion_event(
@@ -644,40 +3349,77 @@ impl EventHandler for MainState {
         _dx: f32, 
         _dy: f32
     ) {
-        // Update cursor position
+        // Update cursor position, in layout units
+        let (x, y) = self.to_layout(x, y);
         self.cursor_position = (x, y);
+        self.update_hover(x, y);
+
+        if self.dragging_slider.is_some() {
+            self.drag_slider_to(x);
+        }
     }
 
     fn draw(&mut self, ctx: &mut Context) -> GameResult {
-        use ggez::graphics::{self, Color};
+        use ggez::graphics::Color;
         graphics::clear(ctx, Color::WHITE);
 
-        // Draw UI based on game state
-        match self.game_state {
-            GameState::Playing => {
-                ui::draw_game_ui(self, ctx)?;
-            },
-            GameState::RoundEnd => {
-                ui::draw_round_end_ui(self, ctx)?;
-            },
-            GameState::GameOver => {
-                ui::draw_game_over_ui(self, ctx)?;
-            },
+        // A failed frame shouldn't take the whole game down - report it and show
+        // the error screen instead. Continue there retries the normal screens.
+        if self.draw_error.is_none()
+            && let Err(e) = self.draw_screen(ctx)
+        {
+            eprintln!("Drawing failed: {}", e);
+            self.draw_error = Some(e.to_string());
+        }
+
+        if self.draw_error.is_some() {
+            ui::draw_error_screen(self, ctx)?;
         }
 
         graphics::present(ctx)?;
+        self.pace_frame();
         Ok(())
     }
 
     fn mouse_button_down_event(
         &mut self,
-        _ctx: &mut Context,
+        ctx: &mut Context,
         button: MouseButton,
         x: f32,
         y: f32,
     ) {
+        // Work in layout units like everything else
+        let (x, y) = self.to_layout(x, y);
+
         if button == MouseButton::Left {
+            // Back to the mouse, so hide the keyboard focus
+            self.keyboard_focus_visible = false;
+
+            // The error screen replaces everything else until it's dismissed
+            if self.draw_error.is_some() {
+                if self.error_continue_rect().contains([x, y]) {
+                    self.draw_error = None;
+                } else if self.error_quit_rect().contains([x, y]) {
+                    event::quit(ctx);
+                }
+                return;
+            }
+
+            // An open overlay takes every click, so none fall through to the screen below
+            if let Some(overlay) = self.top_overlay() {
+                match overlay {
+                    Overlay::Settings => self.handle_settings_click(x, y),
+                    Overlay::Comparison => {}
+                    Overlay::BotDetail => self.handle_bot_detail_click(x, y),
+                }
+                return;
+            }
+
             match self.game_state {
+                GameState::Menu => {
+                    // Handle menu clicks
+                    self.handle_menu_click(x, y);
+                },
                 GameState::Playing => {
                     // Handle UI clicks during gameplay
                     self.handle_game_ui_click(x, y);
@@ -693,4 +3435,350 @@ impl EventHandler for MainState {
             }
         }
     }
-}
\ No newline at end of file
+
+    fn resize_event(&mut self, ctx: &mut Context, width: f32, height: f32) {
+        // Re-map the screen so clicks line up with what's drawn (and maybe switch preset)
+        self.window_size = (width, height);
+        self.apply_layout(ctx);
+    }
+
+    fn mouse_button_up_event(
+        &mut self,
+        _ctx: &mut Context,
+        button: MouseButton,
+        _x: f32,
+        _y: f32,
+    ) {
+        // Releasing a volume slider persists the new value
+        if button == MouseButton::Left && self.dragging_slider.take().is_some() {
+            self.settings.save();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::DamageModel;
+
+    fn playing_game() -> MainState {
+        MainState::headless(GameConfig::default(), 1)
+    }
+
+    #[test]
+    fn round_clock_stands_still_outside_playing() {
+        let mut state = playing_game();
+        state.game_state = GameState::RoundEnd;
+        state.sync_round_timer();
+        let time_left = state.round_time_left();
+
+        // Ten seconds on the results screen: the round and the pause both started that much earlier
+        let wait = Duration::from_secs(10);
+        state.round_start_time -= wait;
+        state.round_paused_at = state.round_paused_at.map(|paused_at| paused_at - wait);
+        state.sync_round_timer();
+        assert_eq!(state.round_time_left(), time_left);
+
+        // And the wait isn't charged to the round once play resumes
+        state.game_state = GameState::Playing;
+        state.sync_round_timer();
+        assert!(time_left - state.round_time_left() < wait / 10);
+    }
+
+    #[test]
+    fn logic_ticks_mine_exactly_one_step_each() {
+        let mut state = playing_game();
+        let frames = 150;
+        for _ in 0..frames {
+            state.run_logic_ticks(1);
+        }
+
+        // The player never upgrades here, so every payout is at the starting rate
+        let steps = LOGIC_STEP * frames;
+        let payouts = (steps.as_nanos() / state.player.mine_rate().as_nanos()) as f32;
+        assert_eq!(state.player.gold, payouts * state.player.gold_per_mine());
+        assert_eq!(state.player.mine_progress, steps - state.player.mine_rate() * payouts as u32);
+    }
+
+    #[test]
+    fn logic_ticks_advance_every_bot_one_step_each() {
+        let mut state = playing_game();
+        state.run_logic_ticks(4);
+
+        // No bot has the gold to upgrade yet, so each has mined exactly four steps' worth
+        for bot in &state.bots {
+            assert_eq!(bot.mine_progress, LOGIC_STEP * 4);
+        }
+    }
+
+    #[test]
+    fn mining_pet_pays_out_on_logic_steps() {
+        let mut state = playing_game();
+        state.pets = vec![Pet { unlocked: true, mining: true, ..Pet::new() }];
+        let steps_to_payout = state.pet_mine_rate().as_nanos().div_ceil(LOGIC_STEP.as_nanos()) as u32;
+
+        for _ in 1..steps_to_payout {
+            state.run_logic_ticks(1);
+        }
+        assert_eq!(state.pets[0].gold_mined, 0.0);
+
+        // Time outside play doesn't count toward the payout
+        state.game_state = GameState::RoundEnd;
+        state.run_logic_ticks(MAX_TICKS_PER_FRAME);
+        state.game_state = GameState::Playing;
+        assert_eq!(state.pets[0].gold_mined, 0.0);
+
+        state.run_logic_ticks(1);
+        assert_eq!(state.pets[0].gold_mined, state.player.gold_per_mine() / 2.0);
+        assert_eq!(state.pets[0].progress, LOGIC_STEP * steps_to_payout - state.pet_mine_rate());
+    }
+
+    #[test]
+    fn hitch_is_capped_at_max_ticks_per_frame() {
+        let mut state = playing_game();
+        state.run_logic_ticks(1000);
+
+        for miner in std::iter::once(&state.player).chain(state.bots.iter()) {
+            assert_eq!(miner.mine_progress, LOGIC_STEP * MAX_TICKS_PER_FRAME);
+        }
+    }
+
+    // Where the center of `rect` (in layout units) is drawn in the window, in pixels
+    fn on_screen_center(state: &MainState, rect: Rect) -> (f32, f32) {
+        ((rect.x + rect.w / 2.0) * state.ui_scale, (rect.y + rect.h / 2.0) * state.ui_scale)
+    }
+
+    #[test]
+    fn clicks_land_on_buttons_after_a_resize() {
+        let mut state = playing_game();
+        state.game_state = GameState::Menu;
+
+        for window_size in [(1920.0, 1080.0), (900.0, 640.0), (1700.0, 1150.0)] {
+            state.window_size = window_size;
+            state.update_layout();
+
+            // The damage model arrow (it wraps, so every click changes it) is hit where it's
+            // drawn, whichever preset the size picked
+            let damage_model = state.config.damage_model.label();
+            let (_, next_model) = state.menu_arrow_rects(3);
+            let (x, y) = on_screen_center(&state, next_model);
+            let (x, y) = state.to_layout(x, y);
+            assert!(next_model.contains([x, y]));
+            state.handle_menu_click(x, y);
+            assert_ne!(state.config.damage_model.label(), damage_model, "window {:?}", window_size);
+        }
+
+        // Start Game too
+        let (x, y) = on_screen_center(&state, state.menu_start_rect());
+        let (x, y) = state.to_layout(x, y);
+        state.handle_menu_click(x, y);
+        assert!(matches!(state.game_state, GameState::Playing));
+    }
+
+    #[test]
+    fn round_nobody_donated_in_has_no_damage_or_winner() {
+        let mut state = playing_game();
+        let health_before: Vec<i32> = std::iter::once(&state.player).chain(state.bots.iter()).map(|miner| miner.health).collect();
+        state.end_round();
+
+        let health_after: Vec<i32> = std::iter::once(&state.player).chain(state.bots.iter()).map(|miner| miner.health).collect();
+        assert_eq!(health_after, health_before);
+        assert!(state.round_damage.iter().all(|breakdown| breakdown.taken == 0 && breakdown.waived == Some("nobody donated")));
+        assert_eq!(state.past_results, vec![false]);
+        assert!(std::iter::once(&state.player).chain(state.bots.iter()).all(|miner| miner.mvp_rounds == 0));
+    }
+
+    #[test]
+    fn dead_bots_never_act() {
+        for turn_based in [false, true] {
+            let mut state = MainState::headless(GameConfig { turn_based, ..GameConfig::default() }, 3);
+            // Plenty to upgrade or donate with, were it alive
+            state.bots[0].gold = 5000.0;
+            let health = state.bots[0].health;
+            state.bots[0].take_damage(health);
+            let dead = state.bots[0];
+
+            if turn_based {
+                state.end_turn();
+            } else {
+                state.run_logic_ticks(MAX_TICKS_PER_FRAME);
+                state.bot_make_decision(0);
+            }
+
+            let bot = &state.bots[0];
+            assert!(!bot.alive);
+            assert_eq!(bot.gold, dead.gold);
+            assert_eq!(bot.total_donated, 0.0);
+            assert_eq!(bot.levels, [0, 0]);
+            assert_eq!(bot.total_gold_mined, dead.total_gold_mined);
+        }
+    }
+
+    #[test]
+    fn pet_unlock_deducts_the_cost_shown() {
+        let mut state = playing_game();
+        state.player.gold = 100_000.0;
+
+        for owned in 0..MAX_PETS {
+            let shown = state.pet_unlock_cost();
+            let gold_before = state.player.gold;
+            state.unlock_pet();
+            assert_eq!(state.pets.len(), owned + 1);
+            assert_eq!(gold_before - state.player.gold, shown);
+        }
+    }
+
+    #[test]
+    fn settings_fit_smallest_window() {
+        let state = playing_game();
+        let screen = Rect::new(0.0, 0.0, WINDOW_WIDTH, WINDOW_HEIGHT);
+        let panel = state.settings_panel_rect();
+        assert!(screen.contains(panel.point()) && screen.contains([panel.right(), panel.bottom()]));
+
+        let mut rects = state.settings_button_rects();
+        assert!(state.can_quit_to_menu());
+        rects.extend((0..VolumeChannel::ALL.len()).map(|index| state.volume_slider_hit_rect(index)));
+        for (i, rect) in rects.iter().enumerate() {
+            assert!(panel.contains(rect.point()) && panel.contains([rect.right(), rect.bottom()]));
+            assert!(rects[i + 1..].iter().all(|other| !rect.overlaps(other)));
+        }
+    }
+
+    #[test]
+    fn compact_gameplay_fits_smallest_window() {
+        let mut state = playing_game();
+        state.settings.layout = LayoutMode::Compact;
+        state.window_size = (WINDOW_WIDTH, WINDOW_HEIGHT);
+        state.update_layout();
+
+        // The pet panel is the right edge of the gameplay screen
+        assert!(state.screen_size.0 >= GAMEPLAY_WIDTH && state.screen_size.1 >= WINDOW_HEIGHT);
+
+        // A live pet, a dead one to revive and gold for the next, so every pet button shows
+        state.pets = vec![Pet { unlocked: true, ..Pet::new() }, Pet { unlocked: true, alive: false, ..Pet::new() }];
+        state.player.gold = 10_000.0;
+        let screen = Rect::new(0.0, 0.0, state.screen_size.0, state.screen_size.1);
+        let rects = state.clickable_rects();
+        assert!(rects.contains(&pet_unlock_button_rect(2)) && rects.contains(&pet_revive_button_rect(1)));
+        for rect in rects {
+            assert!(screen.contains(rect.point()) && screen.contains([rect.right(), rect.bottom()]), "{:?}", rect);
+        }
+    }
+
+    #[test]
+    fn every_miner_keeps_its_own_color() {
+        let config = GameConfig { bot_count: crate::config::MAX_BOTS, ..GameConfig::default() };
+        let state = MainState::headless(config, 5);
+
+        let colors: Vec<_> = std::iter::once(&state.player).chain(state.bots.iter()).map(|miner| miner.color).collect();
+        for (i, color) in colors.iter().enumerate() {
+            assert!(!colors[i + 1..].contains(color));
+        }
+
+        // The nemesis keeps the color of the slot it was spawned in
+        let (slot, nemesis) = state.bots.iter().enumerate().find(|(_, bot)| bot.strategy == Some(BotStrategy::Nemesis)).unwrap();
+        assert_eq!(nemesis.color, BotStrategy::for_index(slot).color());
+    }
+
+    #[test]
+    fn continued_save_resumes_the_random_stream() {
+        let mut state = playing_game();
+        for _ in 0..37 {
+            state.rng.r#gen::<u32>();
+        }
+
+        let json = serde_json::to_string(&state.save_data()).unwrap();
+        let data: SaveData = serde_json::from_str(&json).unwrap();
+        let mut resumed = ChaCha12Rng::seed_from_u64(data.seed);
+        resumed.set_word_pos(data.rng_word_pos);
+
+        for _ in 0..100 {
+            assert_eq!(resumed.r#gen::<u64>(), state.rng.r#gen::<u64>());
+        }
+    }
+
+    #[test]
+    fn continued_game_keeps_the_cheat_flag_and_seed_field() {
+        let mut state = playing_game();
+        state.cheats_used = true;
+        let data = state.save_data();
+
+        let mut resumed = MainState::headless(GameConfig::default(), 2);
+        resumed.game_state = GameState::Menu;
+        resumed.seed_input = "1234".to_string();
+        resumed.resume_game(data);
+
+        assert!(resumed.cheats_used);
+        assert_eq!(resumed.seed, state.seed);
+        assert_eq!(resumed.seed_input, "1234");
+    }
+
+    #[test]
+    fn damage_tooltips_follow_the_damage_model() {
+        let mut state = playing_game();
+        let health = Rect::new(20.0, 125.0, 220.0, 30.0);
+        let donate = donation_button_rect(0);
+
+        for model in [DamageModel::Linear, DamageModel::LastPlaceOnly, DamageModel::TopHalfSafe] {
+            state.config.damage_model = model;
+            for rect in [health, donate] {
+                let text = state.tooltip_at(rect.x + 1.0, rect.y + 1.0).unwrap();
+                assert!(text.contains(model.description()) && !text.contains("shield"));
+            }
+        }
+
+        state.config.first_round_shield = true;
+        assert!(state.tooltip_at(health.x + 1.0, health.y + 1.0).unwrap().contains("Round 1 shield"));
+    }
+
+    #[test]
+    fn quick_match_keeps_the_menu_options() {
+        let mut state = playing_game();
+        state.game_state = GameState::Menu;
+        state.config.bot_count = 2;
+        state.config.hardcore = true;
+
+        state.quick_match();
+        assert_eq!(state.config.bot_count, GameConfig::default().bot_count);
+        assert!(!state.config.hardcore);
+
+        state.show_menu();
+        assert_eq!(state.config.bot_count, 2);
+        assert!(state.config.hardcore);
+    }
+
+    #[test]
+    fn adaptive_bots_judge_health_against_max_health() {
+        let mut state = playing_game();
+        state.config.adaptive_difficulty = true;
+
+        // Full health in a 3 HP game isn't a struggle
+        state.player.max_health = 3;
+        state.player.health = 3;
+        assert_eq!(state.adaptive_factor(), 1.0);
+
+        // But 5 of 20 is critical
+        state.player.max_health = 20;
+        state.player.health = 5;
+        assert_eq!(state.adaptive_factor(), 0.8);
+    }
+
+    #[test]
+    fn menu_fits_smallest_window() {
+        let mut state = MainState::headless(GameConfig::default(), 1);
+        state.save_available = true;
+        let screen = Rect::new(0.0, 0.0, WINDOW_WIDTH, WINDOW_HEIGHT);
+        let panel = state.menu_panel_rect();
+        assert!(screen.contains(panel.point()) && screen.contains([panel.right(), panel.bottom()]));
+
+        let mut rects = vec![state.menu_seed_rect(), state.menu_quick_match_rect(), state.menu_start_rect(), state.menu_continue_rect()];
+        for option in 0..MENU_OPTION_COUNT {
+            let (left, right) = state.menu_arrow_rects(option);
+            rects.extend([left, right]);
+        }
+        for (i, rect) in rects.iter().enumerate() {
+            assert!(panel.contains(rect.point()) && panel.contains([rect.right(), rect.bottom()]));
+            assert!(rects[i + 1..].iter().all(|other| !rect.overlaps(other)));
+        }
+    }
+}
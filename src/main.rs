@@ -1,25 +1,106 @@
 use ggez::{ContextBuilder, GameResult};
 use ggez::event;
 use ggez::conf::{WindowSetup, WindowMode};
+use std::{env, path};
 
 mod miner;
 mod game_state;
 mod ui;
 mod pet;
+mod settings;
+mod audio;
+mod activity;
+mod chatter;
+mod config;
+mod save;
+mod stats;
+mod balance;
 
+use config::{Difficulty, GameConfig, MAX_BOTS, MIN_BOTS};
 use game_state::MainState;
+use settings::Settings;
 
 const WINDOW_WIDTH: f32 = 1920.0;
 const WINDOW_HEIGHT: f32 = 1080.0;
 
+const USAGE: &str = "Usage: Minefest [--bots N] [--difficulty easy|normal|hard] [--rounds N] [--deep-upgrades] [--balance-report N]";
+
+// What the command line asked for
+struct Args {
+    config: GameConfig,
+    balance_games: Option<usize>, // print a balance report over this many games instead of opening the window
+}
+
+// Reads --bots, --difficulty, --rounds and --deep-upgrades into the starting config so testing can skip the menu,
+// and --balance-report for a headless run. Missing or invalid values keep the default.
+fn parse_args() -> Args {
+    let mut config = GameConfig::default();
+    let mut balance_games = None;
+    let mut args = env::args().skip(1);
+
+    while let Some(flag) = args.next() {
+        match flag.as_str() {
+            "--bots" => {
+                match args.next().and_then(|value| value.parse::<usize>().ok()) {
+                    Some(count) => config.bot_count = count.clamp(MIN_BOTS, MAX_BOTS),
+                    None => eprintln!("--bots expects a number from {} to {}", MIN_BOTS, MAX_BOTS),
+                }
+            },
+            "--difficulty" => {
+                match args.next().as_deref().and_then(Difficulty::from_name) {
+                    Some(difficulty) => config.set_difficulty(difficulty),
+                    None => eprintln!("--difficulty expects easy, normal or hard"),
+                }
+            },
+            "--rounds" => {
+                match args.next().and_then(|value| value.parse::<usize>().ok()) {
+                    Some(rounds) if rounds > 0 => config.round_count = rounds,
+                    _ => eprintln!("--rounds expects a number above 0"),
+                }
+            },
+            "--deep-upgrades" => config.deep_upgrades = true,
+            "--balance-report" => {
+                match args.next().and_then(|value| value.parse::<usize>().ok()) {
+                    Some(games) if games > 0 => balance_games = Some(games),
+                    _ => eprintln!("--balance-report expects a number of games above 0"),
+                }
+            },
+            _ => {
+                eprintln!("Unrecognized argument: {}", flag);
+                eprintln!("{}", USAGE);
+            }
+        }
+    }
+
+    Args { config, balance_games }
+}
 
 // Main function to run the game and initialize the state
 fn main() -> GameResult {
-    let (mut ctx, event_loop) = ContextBuilder::new("placeholder_title", "Daniel Zheng")
-        .window_setup(WindowSetup::default().title("Placeholder Title"))
-        .window_mode(WindowMode::default().dimensions(WINDOW_WIDTH, WINDOW_HEIGHT))
-        .build()?;
+    let Args { config, balance_games } = parse_args();
+
+    if let Some(games) = balance_games {
+        balance::run_report(config, games);
+        return Ok(());
+    }
+
+    // Vsync is part of the window setup, so the saved frame rate choice is read before
+    // the window exists (the game state loads the rest of the settings)
+    let vsync = Settings::load().frame_cap.vsync();
+
+    let mut builder = ContextBuilder::new("placeholder_title", "Daniel Zheng")
+        .window_setup(WindowSetup::default().title("Placeholder Title").vsync(vsync))
+        .window_mode(WindowMode::default().dimensions(WINDOW_WIDTH, WINDOW_HEIGHT).resizable(true));
+
+    // Load sounds from ./resources when running through cargo
+    if let Ok(manifest_dir) = env::var("CARGO_MANIFEST_DIR") {
+        let mut resources = path::PathBuf::from(manifest_dir);
+        resources.push("resources");
+        builder = builder.add_resource_path(resources);
+    }
+
+    let (mut ctx, event_loop) = builder.build()?;
     
-    let state = MainState::new(&mut ctx)?;
+    let state = MainState::new(&mut ctx, config)?;
     event::run(ctx, event_loop, state)
 }
\ No newline at end of file
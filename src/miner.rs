@@ -1,136 +1,715 @@
-use ggez::Context;
-use std::time::{Duration, Instant};
+use ggez::graphics::Color;
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
 
 pub const STARTING_HEALTH: i32 = 10;
 
-#[derive(Debug, Clone, Copy)]
+// Health bands as fractions of max health (rounded up): at or below these it shows red / orange
+const CRITICAL_HEALTH_FRACTION: f32 = 0.3;
+const LOW_HEALTH_FRACTION: f32 = 0.6;
+
+// The player's identity color; bots get their slot's strategy color when spawned
+pub const PLAYER_COLOR: Color = Color::new(0.3, 0.7, 0.4, 1.0);
+
+// Highest pickaxe and mine level, and the raised cap for deep upgrades games
+pub const MAX_UPGRADE_LEVEL: usize = 4;
+pub const DEEP_UPGRADE_LEVEL: usize = 8;
+
+// How many recent rounds a miner remembers; bots weigh upgrading against donating by it
+pub const ROUND_MEMORY: usize = 3;
+
+// Ring buffer of the last ROUND_MEMORY round outcomes, the oldest overwritten first
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize)]
+pub struct RoundMemory {
+    lost: [bool; ROUND_MEMORY], // true for a round finished in the bottom half of the field
+    #[serde(default)]
+    donated: [f32; ROUND_MEMORY], // gold donated that round
+    next: usize, // slot the next outcome goes in
+    len: usize, // outcomes stored so far, up to ROUND_MEMORY
+}
+
+impl RoundMemory {
+    pub fn record(&mut self, lost: bool, donated: f32) {
+        self.lost[self.next] = lost;
+        self.donated[self.next] = donated;
+        self.next = (self.next + 1) % ROUND_MEMORY;
+        self.len = (self.len + 1).min(ROUND_MEMORY);
+    }
+
+    // Rounds lost among the remembered ones. Until the buffer wraps the filled slots
+    // are the first `len`, and after that every slot is filled.
+    pub fn losses(&self) -> usize {
+        self.lost.iter().take(self.len).filter(|&&lost| lost).count()
+    }
+
+    // The remembered rounds as (lost, donated), oldest first
+    pub fn rounds(&self) -> Vec<(bool, f32)> {
+        let start = if self.len < ROUND_MEMORY { 0 } else { self.next };
+        (0..self.len)
+            .map(|offset| (start + offset) % ROUND_MEMORY)
+            .map(|slot| (self.lost[slot], self.donated[slot]))
+            .collect()
+    }
+
+    // Mean donation over the remembered rounds, None before the first one
+    pub fn average_donation(&self) -> Option<f32> {
+        (self.len > 0).then(|| self.donated.iter().take(self.len).sum::<f32>() / self.len as f32)
+    }
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
 pub enum MinerType {
     Player,
     Bot,
 }
 
-#[derive(Debug, Clone, Copy)]
+// The upgrades a miner can buy. Everything fixed about one (label, effect, cost curve,
+// look) is its row in UPGRADES and its level is Miner::levels[kind.index()], so the panel,
+// clicks and bots all go through UpgradeKind::ALL instead of naming each upgrade.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum UpgradeKind {
+    Pickaxe,
+    Mine,
+}
+
+impl UpgradeKind {
+    // In the order the upgrades panel lists them; bots break ties the same way
+    pub const ALL: [UpgradeKind; 2] = [UpgradeKind::Pickaxe, UpgradeKind::Mine];
+
+    // Position in ALL, UPGRADES and Miner::levels
+    pub fn index(&self) -> usize {
+        *self as usize
+    }
+
+    pub fn def(&self) -> &'static UpgradeDef {
+        &UPGRADES[self.index()]
+    }
+
+    pub fn label(&self) -> &'static str {
+        self.def().label
+    }
+
+    // What each level improves, for tooltips
+    pub fn effect(&self) -> &'static str {
+        self.def().effect
+    }
+}
+
+// Shape of an upgrade's icon in the upgrades panel and the opponents' level columns
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum UpgradeIcon {
+    Block,
+    Disc,
+}
+
+pub struct UpgradeDef {
+    pub label: &'static str,
+    pub effect: &'static str,
+    pub costs: [f32; MAX_UPGRADE_LEVEL], // price of each standard level
+    pub deep_growth: f32, // each deep upgrade level costs this many times the one before
+    pub color: Color, // its button and level numbers
+    pub icon: UpgradeIcon,
+    pub icon_color: Color,
+}
+
+// One row per UpgradeKind, in UpgradeKind::ALL order
+pub const UPGRADES: [UpgradeDef; UpgradeKind::ALL.len()] = [
+    UpgradeDef {
+        label: "Pickaxe",
+        effect: "Upgrade your pickaxe to mine more often.",
+        costs: [200.0, 400.0, 800.0, 1600.0],
+        deep_growth: 2.0,
+        color: Color::new(0.9, 0.4, 0.3, 1.0), // Coral
+        icon: UpgradeIcon::Block,
+        icon_color: Color::new(0.6, 0.4, 0.2, 1.0), // Brown
+    },
+    UpgradeDef {
+        label: "Mine",
+        effect: "Upgrade your mine to get more gold\nfrom each mining operation.",
+        costs: [100.0, 300.0, 600.0, 1000.0],
+        deep_growth: 1.5,
+        color: Color::new(0.2, 0.4, 0.8, 1.0), // Royal blue
+        icon: UpgradeIcon::Disc,
+        icon_color: Color::new(0.5, 0.5, 0.5, 1.0), // Gray
+    },
+];
+
+// How a bot plays. Each one has a name the UI and activity log use for it.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum BotStrategy {
+    Economy,    // Upgrades first, donates a little at the end of the round
+    Aggressive, // Donates most of its gold at the end of the round
+    Balanced,   // Mix of upgrades and moderate donations
+    Usurper,    // Late in the round, donates just enough to overtake whoever is leading
+    Survivor,   // Late in the round, donates just enough not to finish last and banks the rest
+    Random,     // Fallback for extra bots
+    Nemesis,    // One bot per game: late in the round, donates just enough to beat the player
+}
+
+impl BotStrategy {
+    // Strategy for the bot at `index`, matching the original fixed line-up
+    pub fn for_index(index: usize) -> Self {
+        match index {
+            0 => BotStrategy::Economy,
+            1 => BotStrategy::Aggressive,
+            2 => BotStrategy::Balanced,
+            3 => BotStrategy::Usurper,
+            4 => BotStrategy::Survivor,
+            _ => BotStrategy::Random,
+        }
+    }
+
+    // Color a bot starting out with this strategy is spawned with (Miner::color); each
+    // strategy's is distinct from the others and from the player's
+    pub fn color(&self) -> Color {
+        match self {
+            BotStrategy::Economy => Color::new(0.2, 0.4, 0.8, 1.0),     // Blue
+            BotStrategy::Aggressive => Color::new(0.85, 0.25, 0.2, 1.0), // Red
+            BotStrategy::Balanced => Color::new(0.55, 0.3, 0.75, 1.0),  // Purple
+            BotStrategy::Usurper => Color::new(0.9, 0.5, 0.1, 1.0),     // Orange
+            BotStrategy::Survivor => Color::new(0.1, 0.55, 0.6, 1.0),   // Teal
+            BotStrategy::Random => Color::new(0.55, 0.4, 0.25, 1.0),    // Brown
+            BotStrategy::Nemesis => Color::new(0.6, 0.05, 0.3, 1.0),    // Crimson
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            BotStrategy::Economy => "Greedy",
+            BotStrategy::Aggressive => "Reckless",
+            BotStrategy::Balanced => "Steady",
+            BotStrategy::Usurper => "Usurper",
+            BotStrategy::Survivor => "Survivor",
+            BotStrategy::Random => "Wildcard",
+            BotStrategy::Nemesis => "Nemesis",
+        }
+    }
+
+    // Short description of how it plays, for the optional strategy reveal
+    pub fn playstyle(&self) -> &'static str {
+        match self {
+            BotStrategy::Economy => "Economy",
+            BotStrategy::Aggressive => "Aggressive",
+            BotStrategy::Balanced => "Balanced",
+            BotStrategy::Usurper => "Chases the leader",
+            BotStrategy::Survivor => "Defensive",
+            BotStrategy::Random => "Unpredictable",
+            BotStrategy::Nemesis => "Out to beat you",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
 pub struct Miner {
     pub miner_type: MinerType,
+    pub strategy: Option<BotStrategy>, // None for the player
     pub gold: f32,
     pub donated_gold: f32,
-    pub pickaxe_level: usize,
-    pub mine_level: usize,
-    pub last_mine_time: Instant,
+    #[serde(default)]
+    pub levels: [usize; UpgradeKind::ALL.len()], // by UpgradeKind::index
+    #[serde(default, skip_serializing)]
+    pub pickaxe_level: Option<usize>, // saves from before levels were kept by kind
+    #[serde(default, skip_serializing)]
+    pub mine_level: Option<usize>,
+    #[serde(default)]
+    pub mine_progress: Duration, // logic time mined toward the next payout
     pub health: i32,
+    pub max_health: i32,
     pub alive: bool,
     pub has_donated_this_round: bool,
     pub total_gold_mined: f32,
+    pub total_donated: f32, // across the whole game; donated_gold resets every round
+    #[serde(default)]
+    pub upgrade_spent: f32, // gold spent on upgrades this round
+    pub mvp_rounds: usize, // rounds this game where they donated the most
+    #[serde(default)]
+    pub total_damage_taken: i32, // across the whole game
+    #[serde(default = "default_max_level")]
+    pub max_level: usize, // highest level either upgrade can reach
+    #[serde(default)]
+    pub round_memory: RoundMemory, // recent round outcomes, recorded in end_round
+    #[serde(default)]
+    pub yield_curve: YieldCurve, // the game's, so bots and the player mine by the same rules
+    #[serde(default = "default_color")]
+    pub color: Color, // marks this miner across the UI; assigned at spawn and distinct per miner
+}
+
+// Saves from before miners had their own color; continue_saved_game recolors the bots
+fn default_color() -> Color {
+    PLAYER_COLOR
+}
+
+// Cost curves: price of going from `level` to the next one
+fn upgrade_cost_at(kind: UpgradeKind, level: usize, max_level: usize) -> f32 {
+    if level >= max_level {
+        return f32::MAX; // Can't upgrade further
+    }
+
+    let def = kind.def();
+    match def.costs.get(level) {
+        Some(&cost) => cost,
+        // Deep upgrades keep growing from the last standard level's price
+        None => def.costs[MAX_UPGRADE_LEVEL - 1] * def.deep_growth.powi((level + 1 - MAX_UPGRADE_LEVEL) as i32),
+    }
+}
+
+// Fastest a yield curve can mine. Miner::update mines at most once per logic step, so
+// anything quicker would be cut short there anyway.
+pub const MAX_MINES_PER_SECOND: f32 = 25.0;
+
+// Sanity limits for a custom curve's numbers
+const MAX_YIELD_BASE_GOLD: f32 = 1000.0;
+const MAX_YIELD_GROWTH: f32 = 5.0;
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum CurveShape {
+    Linear,      // each level adds `growth` times the level 0 value
+    Exponential, // each level multiplies the value by 1 + `growth`
+}
+
+impl CurveShape {
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.to_ascii_lowercase().as_str() {
+            "linear" => Some(CurveShape::Linear),
+            "exponential" => Some(CurveShape::Exponential),
+            _ => None,
+        }
+    }
+}
+
+// A tunable yield curve: gold per mining operation grows with mine_level, mining
+// operations per second with pickaxe_level
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct CurveParams {
+    pub shape: CurveShape,
+    pub gold_base: f32,    // gold per mining operation at mine level 0
+    pub gold_growth: f32,  // per mine level
+    pub speed_base: f32,   // mining operations per second at pickaxe level 0
+    pub speed_growth: f32, // per pickaxe level
+}
+
+impl CurveParams {
+    // Steady gains from every level, ending up a little under Classic at level 4
+    pub const LINEAR: CurveParams = CurveParams {
+        shape: CurveShape::Linear,
+        gold_base: 5.0,
+        gold_growth: 1.2,
+        speed_base: 1.0,
+        speed_growth: 2.0,
+    };
+
+    // Slow early levels that pay off more and more, especially with deep upgrades
+    pub const EXPONENTIAL: CurveParams = CurveParams {
+        shape: CurveShape::Exponential,
+        gold_base: 5.0,
+        gold_growth: 0.5,
+        speed_base: 1.0,
+        speed_growth: 0.75,
+    };
+
+    // Multiplier on the level 0 value at `level`
+    fn scale(&self, growth: f32, level: usize) -> f32 {
+        match self.shape {
+            CurveShape::Linear => 1.0 + growth * level as f32,
+            CurveShape::Exponential => (1.0 + growth).powi(level as i32),
+        }
+    }
+
+    // Rejects numbers that would break the economy: non-positive or absurd bases, and
+    // negative (shrinking) or runaway growth
+    pub fn validate(&self) -> Result<(), String> {
+        if !(self.gold_base > 0.0 && self.gold_base <= MAX_YIELD_BASE_GOLD) {
+            return Err(format!("gold_base must be above 0 and at most {}", MAX_YIELD_BASE_GOLD));
+        }
+        if !(self.speed_base > 0.0 && self.speed_base <= MAX_MINES_PER_SECOND) {
+            return Err(format!("speed_base must be above 0 and at most {}", MAX_MINES_PER_SECOND));
+        }
+        for (name, growth) in [("gold_growth", self.gold_growth), ("speed_growth", self.speed_growth)] {
+            if !(0.0..=MAX_YIELD_GROWTH).contains(&growth) {
+                return Err(format!("{} must be from 0 to {}", name, MAX_YIELD_GROWTH));
+            }
+        }
+        Ok(())
+    }
+}
+
+// How mining yield scales with the upgrade levels. Every miner in a game uses the same one.
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+pub enum YieldCurve {
+    #[default]
+    Classic, // the original hand-tuned tables
+    Curve(CurveParams),
+}
+
+impl YieldCurve {
+    pub const LINEAR: YieldCurve = YieldCurve::Curve(CurveParams::LINEAR);
+    pub const EXPONENTIAL: YieldCurve = YieldCurve::Curve(CurveParams::EXPONENTIAL);
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            YieldCurve::Classic => "Classic",
+            curve if *curve == YieldCurve::LINEAR => "Linear",
+            curve if *curve == YieldCurve::EXPONENTIAL => "Exponential",
+            YieldCurve::Curve(_) => "Custom",
+        }
+    }
+
+    // Time between mining operations at `pickaxe_level`
+    pub fn mine_rate(&self, pickaxe_level: usize) -> Duration {
+        match self {
+            YieldCurve::Classic => match pickaxe_level {
+                0 => Duration::from_secs_f32(1.0),    // 1 sec (base)
+                1 => Duration::from_secs_f32(0.75),   // 0.75 sec
+                2 => Duration::from_secs_f32(0.5),    // 0.5 sec
+                3 => Duration::from_secs_f32(0.25),   // 0.25 sec
+                4 => Duration::from_secs_f32(0.1),    // 0.1 sec
+                level => Duration::from_secs_f32(0.1 * 0.8f32.powi(level as i32 - 4)), // Deep upgrades: 20% faster each
+            },
+            YieldCurve::Curve(params) => {
+                let speed = params.speed_base * params.scale(params.speed_growth, pickaxe_level);
+                Duration::from_secs_f32(1.0 / speed.min(MAX_MINES_PER_SECOND))
+            },
+        }
+    }
+
+    // Gold from each mining operation at `mine_level`
+    pub fn gold_per_mine(&self, mine_level: usize) -> f32 {
+        match self {
+            YieldCurve::Classic => match mine_level {
+                0 => 5.0,  // 2g (base)
+                1 => 8.0,  // 3g
+                2 => 12.0,  // 5g
+                3 => 20.0,  // 8g
+                4 => 28.0, // 15g
+                level => 28.0 + (level - 4) as f32 * 10.0, // Deep upgrades: +10g each
+            },
+            YieldCurve::Curve(params) => params.gold_base * params.scale(params.gold_growth, mine_level),
+        }
+    }
+
+    // Average gold mined per second at the given levels
+    pub fn income_per_second(&self, pickaxe_level: usize, mine_level: usize) -> f32 {
+        self.gold_per_mine(mine_level) / self.mine_rate(pickaxe_level).as_secs_f32()
+    }
+}
+
+// Saves from before the cap could be raised were always at the standard one
+fn default_max_level() -> usize {
+    MAX_UPGRADE_LEVEL
 }
 
 impl Miner {
     pub fn new(miner_type: MinerType) -> Self {
         Miner {
             miner_type,
+            strategy: None,
             gold: 0.0,
             donated_gold: 0.0,
-            pickaxe_level: 0,
-            mine_level: 0,
-            last_mine_time: Instant::now(),
+            levels: [0; UpgradeKind::ALL.len()],
+            pickaxe_level: None,
+            mine_level: None,
+            mine_progress: Duration::ZERO,
             health: STARTING_HEALTH,
+            max_health: STARTING_HEALTH,
             alive: true,
             has_donated_this_round: false,
             total_gold_mined: 0.0, // Initialize to 0
+            total_donated: 0.0,
+            upgrade_spent: 0.0,
+            mvp_rounds: 0,
+            total_damage_taken: 0,
+            max_level: MAX_UPGRADE_LEVEL,
+            round_memory: RoundMemory::default(),
+            yield_curve: YieldCurve::Classic,
+            color: PLAYER_COLOR,
         }
     }
 
-    pub fn mine_rate(&self) -> Duration {
-        match self.pickaxe_level {
-            0 => Duration::from_secs_f32(1.0),    // 1 sec (base)
-            1 => Duration::from_secs_f32(0.75),   // 0.75 sec
-            2 => Duration::from_secs_f32(0.5),    // 0.5 sec
-            3 => Duration::from_secs_f32(0.25),   // 0.25 sec
-            4 => Duration::from_secs_f32(0.1),    // 0.1 sec
-            _ => Duration::from_secs_f32(1.0),    // Default to base in case
+    pub fn new_bot(strategy: BotStrategy) -> Self {
+        Miner {
+            strategy: Some(strategy),
+            color: strategy.color(),
+            ..Miner::new(MinerType::Bot)
         }
     }
 
-    pub fn gold_per_mine(&self) -> f32 {
-        match self.mine_level {
-            0 => 5.0,  // 2g (base)
-            1 => 8.0,  // 3g
-            2 => 12.0,  // 5g
-            3 => 20.0,  // 8g
-            4 => 28.0, // 15g
-            _ => 5.0,  // Default to base
+    // Health at or below this is critical: red numbers, and the pulsing vignette for the player
+    pub fn critical_health(&self) -> i32 {
+        (self.max_health as f32 * CRITICAL_HEALTH_FRACTION).ceil() as i32
+    }
+
+    // Health at or below this (and above critical) shows orange
+    pub fn low_health(&self) -> i32 {
+        (self.max_health as f32 * LOW_HEALTH_FRACTION).ceil() as i32
+    }
+
+    // Name shown in the UI and activity log
+    pub fn display_name(&self) -> String {
+        match self.strategy {
+            Some(strategy) => format!("Bot {}", strategy.name()),
+            None => "You".to_string(),
         }
     }
 
-    pub fn pickaxe_upgrade_cost(&self) -> f32 {
-        match self.pickaxe_level {
-            0 => 200.0,  // Level 1: 200g
-            1 => 400.0,  // Level 2: 400g
-            2 => 800.0,  // Level 3: 800g
-            3 => 1600.0, // Level 4: 1600g
-            _ => f32::MAX, // Can't upgrade further
+    pub fn mine_rate(&self) -> Duration {
+        self.yield_curve.mine_rate(self.level(UpgradeKind::Pickaxe))
+    }
+
+    pub fn gold_per_mine(&self) -> f32 {
+        self.yield_curve.gold_per_mine(self.level(UpgradeKind::Mine))
+    }
+
+    // Average gold mined per second at the current levels
+    pub fn income_per_second(&self) -> f32 {
+        self.yield_curve.income_per_second(self.level(UpgradeKind::Pickaxe), self.level(UpgradeKind::Mine))
+    }
+
+    // Turn-based mining: adds what `duration` of mining at the current levels brings in
+    // all at once, and returns it
+    pub fn mine_for(&mut self, duration: Duration) -> f32 {
+        if !self.alive {
+            return 0.0;
         }
+
+        let gold_amount = self.income_per_second() * duration.as_secs_f32();
+        self.gold += gold_amount;
+        self.total_gold_mined += gold_amount;
+        gold_amount
+    }
+
+    pub fn level(&self, kind: UpgradeKind) -> usize {
+        self.levels[kind.index()]
     }
 
-    pub fn mine_upgrade_cost(&self) -> f32 {
-        match self.mine_level {
-            0 => 100.0,  // Level 1: 100g
-            1 => 300.0,  // Level 2: 300g
-            2 => 600.0,  // Level 3: 600g
-            3 => 1000.0, // Level 4: 1000g
-            _ => f32::MAX, // Can't upgrade further
+    pub fn level_mut(&mut self, kind: UpgradeKind) -> &mut usize {
+        &mut self.levels[kind.index()]
+    }
+
+    // Saves from before levels were kept by kind stored them in their own fields
+    pub fn restore_legacy_levels(&mut self) {
+        if let Some(level) = self.pickaxe_level.take() {
+            *self.level_mut(UpgradeKind::Pickaxe) = level;
         }
+        if let Some(level) = self.mine_level.take() {
+            *self.level_mut(UpgradeKind::Mine) = level;
+        }
+    }
+
+    // Price of the next level of `kind`
+    pub fn upgrade_cost(&self, kind: UpgradeKind) -> f32 {
+        upgrade_cost_at(kind, self.level(kind), self.max_level)
+    }
+
+    pub fn maxed(&self, kind: UpgradeKind) -> bool {
+        self.level(kind) >= self.max_level
+    }
+
+    // The next level of `kind` exists and fits within `budget`
+    pub fn can_upgrade(&self, kind: UpgradeKind, budget: f32) -> bool {
+        !self.maxed(kind) && budget >= self.upgrade_cost(kind)
     }
 
-    pub fn update(&mut self, _ctx: &Context) {
+    // One fixed logic step of real-time mining. Any time past a payout carries over to the
+    // next one, so the rate doesn't depend on how the steps line up with it.
+    pub fn update(&mut self, step: Duration) {
         if !self.alive {
             return;
         }
 
-        let now = Instant::now();
-        let elapsed = now.duration_since(self.last_mine_time);
+        self.mine_progress += step;
         
-        if elapsed >= self.mine_rate() {
+        let mine_rate = self.mine_rate();
+        if self.mine_progress >= mine_rate {
             // Mine gold
             let gold_amount = self.gold_per_mine();
             self.gold += gold_amount;
             self.total_gold_mined += gold_amount; // Track the total gold mined
-            self.last_mine_time = now;
+            self.mine_progress -= mine_rate;
         }
     }
 
-    pub fn upgrade_pickaxe(&mut self) -> bool {
-        if self.pickaxe_level >= 4 || self.gold < self.pickaxe_upgrade_cost() {
+    // Gold the current upgrade levels cost to reach, priced by the same cost curves
+    // the upgrades charge
+    pub fn upgrade_investment(&self) -> f32 {
+        UpgradeKind::ALL
+            .iter()
+            .flat_map(|&kind| (0..self.level(kind)).map(move |level| upgrade_cost_at(kind, level, self.max_level)))
+            .sum()
+    }
+
+    // Returns whether the upgrade went through. Nothing changes when it doesn't
+    // (already max level or not enough gold).
+    pub fn upgrade(&mut self, kind: UpgradeKind) -> bool {
+        if !self.can_upgrade(kind, self.gold) {
             return false;
         }
 
-        self.gold -= self.pickaxe_upgrade_cost();
-        self.pickaxe_level += 1;
+        let cost = self.upgrade_cost(kind);
+        self.upgrade_spent += cost;
+        self.gold -= cost;
+        *self.level_mut(kind) += 1;
         true
     }
 
-    pub fn upgrade_mine(&mut self) -> bool {
-        if self.mine_level >= 4 || self.gold < self.mine_upgrade_cost() {
-            return false;
+    // What the current top level of `kind` cost, priced by the same cost curve it was bought on
+    pub fn top_level_cost(&self, kind: UpgradeKind) -> f32 {
+        match self.level(kind) {
+            0 => 0.0,
+            level => upgrade_cost_at(kind, level - 1, self.max_level),
         }
+    }
 
-        self.gold -= self.mine_upgrade_cost();
-        self.mine_level += 1;
-        true
+    // Reverts the top level of `kind` with a full refund. Returns the refund (0 when there's
+    // no level to take back).
+    pub fn undo_upgrade(&mut self, kind: UpgradeKind) -> f32 {
+        if self.level(kind) == 0 {
+            return 0.0;
+        }
+
+        let refund = self.top_level_cost(kind);
+        *self.level_mut(kind) -= 1;
+        self.gold += refund;
+        self.upgrade_spent = (self.upgrade_spent - refund).max(0.0);
+        refund
     }
 
-    pub fn contribute_gold(&mut self, amount: f32) {
-        if amount <= self.gold {
-            self.gold -= amount;
-            self.donated_gold += amount;
-            self.has_donated_this_round = true;
+    // Debug shortcut: every upgrade to the top level without spending gold
+    pub fn force_max_upgrades(&mut self) {
+        for kind in UpgradeKind::ALL {
+            *self.level_mut(kind) = self.max_level;
         }
     }
 
+    // Donates `amount`, or everything on hand if that's less, so gold can never go negative.
+    // Returns what was actually donated.
+    pub fn contribute_gold(&mut self, amount: f32) -> f32 {
+        let amount = amount.min(self.gold);
+        if amount <= 0.0 {
+            return 0.0;
+        }
+
+        self.gold -= amount;
+        self.donated_gold += amount;
+        self.total_donated += amount;
+        self.has_donated_this_round = true;
+        amount
+    }
+
+    // Clears everything that only lasts one round. Anything per-round added to
+    // Miner should be reset here; health, levels and lifetime totals carry over.
+    pub fn reset_for_new_round(&mut self) {
+        self.donated_gold = 0.0;
+        self.upgrade_spent = 0.0;
+        self.has_donated_this_round = false;
+    }
+
     pub fn take_damage(&mut self, damage: i32) {
+        self.total_damage_taken += damage;
         self.health -= damage;
         if self.health <= 0 {
             self.alive = false;
             self.health = 0;
         }
     }
-}
\ No newline at end of file
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reset_for_new_round_clears_only_per_round_fields() {
+        let mut miner = Miner {
+            gold: 300.0,
+            levels: [2, 1],
+            health: 6,
+            total_gold_mined: 900.0,
+            mvp_rounds: 1,
+            total_damage_taken: 4,
+            ..Miner::new_bot(BotStrategy::Balanced)
+        };
+        miner.contribute_gold(120.0);
+        miner.upgrade_spent = 200.0;
+        miner.round_memory.record(true, 120.0);
+        let before = miner;
+
+        miner.reset_for_new_round();
+
+        assert_eq!(miner.donated_gold, 0.0);
+        assert_eq!(miner.upgrade_spent, 0.0);
+        assert!(!miner.has_donated_this_round);
+
+        // Everything else carries over to the next round
+        assert_eq!(miner.gold, before.gold);
+        assert_eq!(miner.total_donated, before.total_donated);
+        assert_eq!(miner.levels, before.levels);
+        assert_eq!((miner.health, miner.alive), (before.health, before.alive));
+        assert_eq!(miner.total_gold_mined, before.total_gold_mined);
+        assert_eq!(miner.mvp_rounds, before.mvp_rounds);
+        assert_eq!(miner.total_damage_taken, before.total_damage_taken);
+        assert_eq!(miner.round_memory.rounds(), before.round_memory.rounds());
+        assert_eq!(miner.mine_progress, before.mine_progress);
+    }
+
+    #[test]
+    fn upgrading_past_the_cap_is_rejected() {
+        for max_level in [MAX_UPGRADE_LEVEL, DEEP_UPGRADE_LEVEL] {
+            let mut miner = Miner {
+                gold: 1_000_000.0,
+                max_level,
+                ..Miner::new(MinerType::Player)
+            };
+            for kind in UpgradeKind::ALL {
+                while miner.upgrade(kind) {}
+                assert_eq!(miner.level(kind), max_level);
+                assert!(miner.maxed(kind));
+            }
+
+            let capped = miner;
+            for kind in UpgradeKind::ALL {
+                assert!(!miner.can_upgrade(kind, f32::MAX));
+                assert!(!miner.upgrade(kind));
+            }
+            assert_eq!(miner.gold, capped.gold);
+            assert_eq!(miner.upgrade_spent, capped.upgrade_spent);
+        }
+    }
+
+    #[test]
+    fn contribute_gold_never_makes_gold_negative() {
+        let mut miner = Miner { gold: 50.0, ..Miner::new(MinerType::Player) };
+
+        // More than it has: only what it has goes in
+        assert_eq!(miner.contribute_gold(80.0), 50.0);
+        assert_eq!(miner.gold, 0.0);
+        assert_eq!(miner.donated_gold, 50.0);
+
+        // Nothing left, or a negative amount: nothing changes
+        assert_eq!(miner.contribute_gold(10.0), 0.0);
+        miner.gold = 20.0;
+        assert_eq!(miner.contribute_gold(-5.0), 0.0);
+        assert_eq!(miner.gold, 20.0);
+        assert_eq!(miner.donated_gold, 50.0);
+    }
+
+    #[test]
+    fn deep_upgrades_grow_from_the_last_standard_price() {
+        let kind = UpgradeKind::Mine;
+        assert_eq!(upgrade_cost_at(kind, MAX_UPGRADE_LEVEL - 1, DEEP_UPGRADE_LEVEL), 1000.0);
+        assert_eq!(upgrade_cost_at(kind, MAX_UPGRADE_LEVEL, DEEP_UPGRADE_LEVEL), 1500.0);
+        assert_eq!(upgrade_cost_at(kind, MAX_UPGRADE_LEVEL + 1, DEEP_UPGRADE_LEVEL), 2250.0);
+        assert_eq!(upgrade_cost_at(UpgradeKind::Pickaxe, MAX_UPGRADE_LEVEL + 1, DEEP_UPGRADE_LEVEL), 6400.0);
+    }
+
+    #[test]
+    fn old_saves_keep_their_upgrade_levels() {
+        let mut json = serde_json::to_value(Miner::new(MinerType::Player)).unwrap();
+        let fields = json.as_object_mut().unwrap();
+        fields.remove("levels");
+        fields.insert("pickaxe_level".to_string(), 3.into());
+        fields.insert("mine_level".to_string(), 1.into());
+
+        let mut miner: Miner = serde_json::from_value(json).unwrap();
+        miner.restore_legacy_levels();
+        assert_eq!((miner.level(UpgradeKind::Pickaxe), miner.level(UpgradeKind::Mine)), (3, 1));
+    }
+}
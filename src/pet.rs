@@ -1,11 +1,78 @@
-use std::time::Instant;
+use serde::{Deserialize, Serialize};
+use std::time::{Duration, Instant};
 
+// Pet slots: the player can own up to this many pets at once
+pub const MAX_PETS: usize = 3;
+
+// Gold to bring a dead pet back
+pub const PET_REVIVE_COST: f32 = 2000.0;
+
+// Cosmetic color picked in the pet panel. Status colors (mining, searching, dead)
+// still take over while they apply.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum PetColor {
+    Blue,
+    Purple,
+    Pink,
+    Teal,
+    Brown,
+}
+
+impl PetColor {
+    pub const ALL: [PetColor; 5] = [
+        PetColor::Blue,
+        PetColor::Purple,
+        PetColor::Pink,
+        PetColor::Teal,
+        PetColor::Brown,
+    ];
+
+    // The color `offset` places after this one in ALL, wrapping around
+    pub fn after(&self, offset: usize) -> PetColor {
+        let index = PetColor::ALL.iter().position(|color| color == self).unwrap_or(0);
+        PetColor::ALL[(index + offset) % PetColor::ALL.len()]
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "blue" => Some(PetColor::Blue),
+            "purple" => Some(PetColor::Purple),
+            "pink" => Some(PetColor::Pink),
+            "teal" => Some(PetColor::Teal),
+            "brown" => Some(PetColor::Brown),
+            _ => None,
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            PetColor::Blue => "blue",
+            PetColor::Purple => "purple",
+            PetColor::Pink => "pink",
+            PetColor::Teal => "teal",
+            PetColor::Brown => "brown",
+        }
+    }
+}
+
+#[derive(Clone, Serialize, Deserialize)]
 pub struct Pet {
     pub unlocked: bool,
     pub alive: bool,
     pub mining: bool,
     pub searching: bool,
-    pub last_mine_time: Instant,
+    #[serde(default)]
+    pub progress: Duration, // logic time toward the next payout or loot check
+    pub color: PetColor,
+    // What the pet did for the player this game, for the game over summary
+    #[serde(default)]
+    pub gold_mined: f32,
+    #[serde(default)]
+    pub loot_found: f32,
+    #[serde(default)]
+    pub hits_absorbed: usize,
+    #[serde(skip)]
+    pub loot_found_at: Option<Instant>, // last loot find, for the sparkle on its icon
 }
 
 impl Pet {
@@ -15,7 +82,12 @@ impl Pet {
             alive: true,
             mining: false,
             searching: false,
-            last_mine_time: Instant::now(),
+            progress: Duration::ZERO,
+            color: PetColor::Blue,
+            gold_mined: 0.0,
+            loot_found: 0.0,
+            hits_absorbed: 0,
+            loot_found_at: None,
         }
     }
     
@@ -41,6 +113,29 @@ impl Pet {
         }
     }
     
+    // Back from the dead, idle, as if freshly unlocked. Its totals for the game stay.
+    pub fn revive(&mut self) {
+        if self.unlocked && !self.alive {
+            self.alive = true;
+            self.mining = false;
+            self.searching = false;
+            self.progress = Duration::ZERO;
+            self.loot_found_at = None;
+        }
+    }
+
+    // One fixed logic step toward whatever the pet is doing. Returns whether `interval` came
+    // due; any time past it carries over, the same way a miner's payouts do.
+    pub fn advance(&mut self, step: Duration, interval: Duration) -> bool {
+        self.progress += step;
+        if self.progress >= interval {
+            self.progress -= interval;
+            true
+        } else {
+            false
+        }
+    }
+
     pub fn take_hit(&mut self) {
         if self.alive {
             self.alive = false;
@@ -0,0 +1,71 @@
+use serde::{Deserialize, Serialize};
+use std::fs;
+
+use crate::config::GameConfig;
+use crate::miner::Miner;
+use crate::pet::Pet;
+
+// Auto-save written at the start of every round so an interrupted game can be resumed
+pub const SAVE_FILE: &str = "savegame.json";
+
+#[derive(Serialize, Deserialize)]
+pub struct SaveData {
+    pub player: Miner,
+    pub bots: Vec<Miner>,
+    #[serde(default)]
+    pub pets: Vec<Pet>,
+    #[serde(default, skip_serializing)]
+    pub pet: Option<Pet>, // saves from before multiple pets had a single one
+    pub config: GameConfig,
+    pub current_round: usize,
+    pub past_results: Vec<bool>,
+    pub total_gold_earned: f32,
+    pub seed: u64,
+    #[serde(default)]
+    pub elimination_order: Vec<usize>,
+    #[serde(default)]
+    pub first_blood_round: Option<usize>,
+    #[serde(default)]
+    pub time_played: f32, // seconds of finished rounds
+    #[serde(default)]
+    pub max_possible_damage: i32,
+    pub round_time_left: f32, // seconds; Instants can't be saved, so the clock is rebuilt from this
+    #[serde(default)]
+    pub rng_word_pos: u128, // how far into the seed's random stream the game had got
+    #[serde(default)]
+    pub cheats_used: bool, // keeps a continued game out of lifetime stats if it was before
+}
+
+impl SaveData {
+    pub fn write(&self) {
+        let result = serde_json::to_string(self)
+            .map_err(|e| e.to_string())
+            .and_then(|json| fs::write(SAVE_FILE, json).map_err(|e| e.to_string()));
+
+        // A failed auto-save shouldn't interrupt the game
+        if let Err(e) = result {
+            eprintln!("Could not save game: {}", e);
+        }
+    }
+
+    // None if there's no save or it can't be read
+    pub fn load() -> Option<SaveData> {
+        let json = fs::read_to_string(SAVE_FILE).ok()?;
+        match serde_json::from_str(&json) {
+            Ok(data) => Some(data),
+            Err(e) => {
+                eprintln!("Ignoring unreadable save file: {}", e);
+                None
+            }
+        }
+    }
+}
+
+pub fn save_exists() -> bool {
+    fs::metadata(SAVE_FILE).is_ok()
+}
+
+pub fn delete_save() {
+    // Nothing to do if it's already gone
+    let _ = fs::remove_file(SAVE_FILE);
+}
@@ -0,0 +1,342 @@
+use std::fs;
+use std::time::Duration;
+
+use crate::pet::PetColor;
+
+// Player preferences, saved to a simple key=value file next to the game
+pub const SETTINGS_FILE: &str = "settings.cfg";
+
+// Most decimal places the gold display can be set to
+pub const MAX_GOLD_DECIMALS: usize = 2;
+
+// Gold the auto-upgrade assistant leaves untouched for donations
+pub const AUTO_UPGRADE_RESERVES: [f32; 5] = [0.0, 100.0, 250.0, 500.0, 1000.0];
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum VolumeChannel {
+    Master,
+    Sfx,
+    Music,
+}
+
+impl VolumeChannel {
+    pub const ALL: [VolumeChannel; 3] = [VolumeChannel::Master, VolumeChannel::Sfx, VolumeChannel::Music];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            VolumeChannel::Master => "Master",
+            VolumeChannel::Sfx => "Sound Effects",
+            VolumeChannel::Music => "Music",
+        }
+    }
+}
+
+// How big the UI is drawn. Compact shrinks the layout so the whole gameplay screen fits
+// windows around 800x600; Spacious draws it scaled up for large windows.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum LayoutMode {
+    Auto, // picks by window size
+    Compact,
+    Spacious,
+}
+
+impl LayoutMode {
+    pub fn label(&self) -> &'static str {
+        match self {
+            LayoutMode::Auto => "Auto",
+            LayoutMode::Compact => "Compact",
+            LayoutMode::Spacious => "Spacious",
+        }
+    }
+
+    pub fn next(&self) -> Self {
+        match self {
+            LayoutMode::Auto => LayoutMode::Compact,
+            LayoutMode::Compact => LayoutMode::Spacious,
+            LayoutMode::Spacious => LayoutMode::Auto,
+        }
+    }
+
+    fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "auto" => Some(LayoutMode::Auto),
+            "compact" => Some(LayoutMode::Compact),
+            "spacious" => Some(LayoutMode::Spacious),
+            _ => None,
+        }
+    }
+
+    fn name(&self) -> &'static str {
+        match self {
+            LayoutMode::Auto => "auto",
+            LayoutMode::Compact => "compact",
+            LayoutMode::Spacious => "spacious",
+        }
+    }
+}
+
+// Frame rate limit for drawing. Game logic runs on its own fixed timestep, so this only
+// changes how smooth the screen is (and how hard the CPU works), never gameplay speed.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum FrameCap {
+    Vsync, // wait for the display's refresh; only takes effect at startup
+    Fps30,
+    Fps60,
+    Fps144,
+    Uncapped,
+}
+
+impl FrameCap {
+    pub fn label(&self) -> &'static str {
+        match self {
+            FrameCap::Vsync => "Vsync",
+            FrameCap::Fps30 => "30 FPS",
+            FrameCap::Fps60 => "60 FPS",
+            FrameCap::Fps144 => "144 FPS",
+            FrameCap::Uncapped => "Uncapped",
+        }
+    }
+
+    pub fn next(&self) -> Self {
+        match self {
+            FrameCap::Vsync => FrameCap::Fps30,
+            FrameCap::Fps30 => FrameCap::Fps60,
+            FrameCap::Fps60 => FrameCap::Fps144,
+            FrameCap::Fps144 => FrameCap::Uncapped,
+            FrameCap::Uncapped => FrameCap::Vsync,
+        }
+    }
+
+    // Whether the window is created with vsync
+    pub fn vsync(&self) -> bool {
+        *self == FrameCap::Vsync
+    }
+
+    // Shortest time between frames, for the capped rates
+    pub fn frame_interval(&self) -> Option<Duration> {
+        let fps = match self {
+            FrameCap::Fps30 => 30,
+            FrameCap::Fps60 => 60,
+            FrameCap::Fps144 => 144,
+            FrameCap::Vsync | FrameCap::Uncapped => return None,
+        };
+        Some(Duration::from_secs(1) / fps)
+    }
+
+    fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "vsync" => Some(FrameCap::Vsync),
+            "30" => Some(FrameCap::Fps30),
+            "60" => Some(FrameCap::Fps60),
+            "144" => Some(FrameCap::Fps144),
+            "uncapped" => Some(FrameCap::Uncapped),
+            _ => None,
+        }
+    }
+
+    fn name(&self) -> &'static str {
+        match self {
+            FrameCap::Vsync => "vsync",
+            FrameCap::Fps30 => "30",
+            FrameCap::Fps60 => "60",
+            FrameCap::Fps144 => "144",
+            FrameCap::Uncapped => "uncapped",
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Settings {
+    pub master_volume: f32, // 0.0 to 1.0, multiplies the other two
+    pub sfx_volume: f32,
+    pub music_volume: f32,
+    pub animations: bool, // decorative UI motion, off for a static UI
+    pub layout: LayoutMode,
+    pub auto_upgrade: bool, // buys the player's upgrades automatically
+    pub auto_upgrade_reserve: f32, // gold auto-upgrade never spends
+    pub gold_decimals: usize, // decimal places shown for gold amounts (0 to 2)
+    pub low_health_vignette: bool, // red pulse around the screen at critical health
+    pub activity_timestamps: bool, // round time in front of each activity log entry
+    pub reveal_strategies: bool, // bots' playstyles shown in the opponents panel
+    pub show_activity_log: bool, // Game Activity panel drawn during play
+    pub frame_cap: FrameCap,
+    pub pet_color: PetColor, // picked for the first pet, kept for the next game's; later pets follow on from it
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Settings {
+            master_volume: 0.8,
+            sfx_volume: 1.0,
+            music_volume: 0.6,
+            animations: true,
+            layout: LayoutMode::Auto,
+            auto_upgrade: false,
+            auto_upgrade_reserve: 100.0,
+            gold_decimals: 0,
+            low_health_vignette: true,
+            activity_timestamps: true,
+            reveal_strategies: false,
+            show_activity_log: true,
+            frame_cap: FrameCap::Vsync, // ggez's default window setup
+            pet_color: PetColor::Blue,
+        }
+    }
+}
+
+impl Settings {
+    // Loads saved settings, falling back to defaults for a missing file or bad values
+    pub fn load() -> Self {
+        let mut settings = Settings::default();
+
+        if let Ok(contents) = fs::read_to_string(SETTINGS_FILE) {
+            for line in contents.lines() {
+                if let Some((key, value)) = line.split_once('=') {
+                    settings.apply(key.trim(), value.trim());
+                }
+            }
+        }
+
+        settings
+    }
+
+    pub fn save(&self) {
+        let contents = format!(
+            "master_volume={}\nsfx_volume={}\nmusic_volume={}\nanimations={}\nlayout={}\nauto_upgrade={}\nauto_upgrade_reserve={}\ngold_decimals={}\nlow_health_vignette={}\nactivity_timestamps={}\nreveal_strategies={}\nshow_activity_log={}\nframe_cap={}\npet_color={}\n",
+            self.master_volume,
+            self.sfx_volume,
+            self.music_volume,
+            self.animations,
+            self.layout.name(),
+            self.auto_upgrade,
+            self.auto_upgrade_reserve,
+            self.gold_decimals,
+            self.low_health_vignette,
+            self.activity_timestamps,
+            self.reveal_strategies,
+            self.show_activity_log,
+            self.frame_cap.name(),
+            self.pet_color.name(),
+        );
+
+        // Failing to save preferences shouldn't interrupt the game
+        if let Err(e) = fs::write(SETTINGS_FILE, contents) {
+            eprintln!("Could not save settings: {}", e);
+        }
+    }
+
+    fn apply(&mut self, key: &str, value: &str) {
+        match key {
+            "master_volume" => Self::parse_volume(value, &mut self.master_volume),
+            "sfx_volume" => Self::parse_volume(value, &mut self.sfx_volume),
+            "music_volume" => Self::parse_volume(value, &mut self.music_volume),
+            "layout" => {
+                if let Some(layout) = LayoutMode::from_name(value) {
+                    self.layout = layout;
+                }
+            },
+            "animations" => {
+                if let Ok(enabled) = value.parse::<bool>() {
+                    self.animations = enabled;
+                }
+            },
+            "auto_upgrade" => {
+                if let Ok(enabled) = value.parse::<bool>() {
+                    self.auto_upgrade = enabled;
+                }
+            },
+            "auto_upgrade_reserve" => {
+                if let Ok(reserve) = value.parse::<f32>() {
+                    self.auto_upgrade_reserve = reserve.max(0.0);
+                }
+            },
+            "gold_decimals" => {
+                if let Ok(decimals) = value.parse::<usize>() {
+                    self.gold_decimals = decimals.min(MAX_GOLD_DECIMALS);
+                }
+            },
+            "low_health_vignette" => {
+                if let Ok(enabled) = value.parse::<bool>() {
+                    self.low_health_vignette = enabled;
+                }
+            },
+            "activity_timestamps" => {
+                if let Ok(enabled) = value.parse::<bool>() {
+                    self.activity_timestamps = enabled;
+                }
+            },
+            "reveal_strategies" => {
+                if let Ok(enabled) = value.parse::<bool>() {
+                    self.reveal_strategies = enabled;
+                }
+            },
+            "show_activity_log" => {
+                if let Ok(enabled) = value.parse::<bool>() {
+                    self.show_activity_log = enabled;
+                }
+            },
+            "frame_cap" => {
+                if let Some(frame_cap) = FrameCap::from_name(value) {
+                    self.frame_cap = frame_cap;
+                }
+            },
+            "pet_color" => {
+                if let Some(color) = PetColor::from_name(value) {
+                    self.pet_color = color;
+                }
+            },
+            _ => {} // Ignore unknown keys from older/newer versions
+        }
+    }
+
+    // Steps through AUTO_UPGRADE_RESERVES, wrapping around
+    pub fn cycle_auto_upgrade_reserve(&mut self) {
+        let index = AUTO_UPGRADE_RESERVES
+            .iter()
+            .position(|reserve| *reserve == self.auto_upgrade_reserve)
+            .map_or(0, |i| (i + 1) % AUTO_UPGRADE_RESERVES.len());
+        self.auto_upgrade_reserve = AUTO_UPGRADE_RESERVES[index];
+    }
+
+    // 0 -> 1 -> 2 -> back to whole numbers
+    pub fn cycle_gold_decimals(&mut self) {
+        self.gold_decimals = (self.gold_decimals + 1) % (MAX_GOLD_DECIMALS + 1);
+    }
+
+    fn parse_volume(value: &str, target: &mut f32) {
+        if let Ok(volume) = value.parse::<f32>() {
+            *target = volume.clamp(0.0, 1.0);
+        }
+    }
+
+    pub fn volume(&self, channel: VolumeChannel) -> f32 {
+        match channel {
+            VolumeChannel::Master => self.master_volume,
+            VolumeChannel::Sfx => self.sfx_volume,
+            VolumeChannel::Music => self.music_volume,
+        }
+    }
+
+    pub fn set_volume(&mut self, channel: VolumeChannel, volume: f32) {
+        let volume = volume.clamp(0.0, 1.0);
+        match channel {
+            VolumeChannel::Master => self.master_volume = volume,
+            VolumeChannel::Sfx => self.sfx_volume = volume,
+            VolumeChannel::Music => self.music_volume = volume,
+        }
+    }
+
+    // Gold amount at the picked precision (whole numbers by default)
+    pub fn format_gold(&self, amount: f32) -> String {
+        format!("{:.*}", self.gold_decimals, amount)
+    }
+
+    // Final gains after applying the master volume
+    pub fn effective_sfx_volume(&self) -> f32 {
+        self.master_volume * self.sfx_volume
+    }
+
+    pub fn effective_music_volume(&self) -> f32 {
+        self.master_volume * self.music_volume
+    }
+}
@@ -0,0 +1,110 @@
+use std::fs;
+
+// Lifetime record across every game played, saved next to the game like the settings
+pub const STATS_FILE: &str = "stats.cfg";
+
+#[derive(Debug, Clone, Default)]
+pub struct LifetimeStats {
+    pub games_played: u32,
+    pub games_won: u32, // games the player survived to the end
+    pub hardcore_played: u32, // hardcore games are their own category, not part of the counts above
+    pub hardcore_won: u32,
+    pub pets_lost: u32, // pets that died taking a hit for the player
+    pub pets_survived: u32, // pets still alive when a game ended
+}
+
+impl LifetimeStats {
+    // Loads saved stats, starting from zero for a missing file or bad values
+    pub fn load() -> Self {
+        let mut stats = LifetimeStats::default();
+
+        if let Ok(contents) = fs::read_to_string(STATS_FILE) {
+            for line in contents.lines() {
+                if let Some((key, value)) = line.split_once('=') {
+                    stats.apply(key.trim(), value.trim());
+                }
+            }
+        }
+
+        stats
+    }
+
+    pub fn save(&self) {
+        let contents = format!(
+            "games_played={}\ngames_won={}\nhardcore_played={}\nhardcore_won={}\npets_lost={}\npets_survived={}\n",
+            self.games_played,
+            self.games_won,
+            self.hardcore_played,
+            self.hardcore_won,
+            self.pets_lost,
+            self.pets_survived,
+        );
+
+        // Failing to save stats shouldn't interrupt the game
+        if let Err(e) = fs::write(STATS_FILE, contents) {
+            eprintln!("Could not save stats: {}", e);
+        }
+    }
+
+    fn apply(&mut self, key: &str, value: &str) {
+        match key {
+            "games_played" => Self::parse_count(value, &mut self.games_played),
+            "games_won" => Self::parse_count(value, &mut self.games_won),
+            "hardcore_played" => Self::parse_count(value, &mut self.hardcore_played),
+            "hardcore_won" => Self::parse_count(value, &mut self.hardcore_won),
+            "pets_lost" => Self::parse_count(value, &mut self.pets_lost),
+            "pets_survived" => Self::parse_count(value, &mut self.pets_survived),
+            _ => {} // Ignore unknown keys from older/newer versions
+        }
+    }
+
+    fn parse_count(value: &str, target: &mut u32) {
+        if let Ok(count) = value.parse::<u32>() {
+            *target = count;
+        }
+    }
+
+    pub fn record_game(&mut self, won: bool, hardcore: bool) {
+        let (played, wins) = if hardcore {
+            (&mut self.hardcore_played, &mut self.hardcore_won)
+        } else {
+            (&mut self.games_played, &mut self.games_won)
+        };
+
+        *played += 1;
+        if won {
+            *wins += 1;
+        }
+        self.save();
+    }
+
+    // Counted as it happens, so a pet lost in a run that's later abandoned still counts
+    pub fn record_pet_lost(&mut self) {
+        self.pets_lost += 1;
+        self.save();
+    }
+
+    pub fn record_pets_survived(&mut self, count: u32) {
+        if count > 0 {
+            self.pets_survived += count;
+            self.save();
+        }
+    }
+
+    // Back to a clean slate, on disk as well as in memory
+    pub fn reset(&mut self) {
+        *self = LifetimeStats::default();
+
+        // Nothing to do if it's already gone
+        let _ = fs::remove_file(STATS_FILE);
+    }
+
+    // Percentage of games won, None until a game has been played
+    pub fn win_rate(&self) -> Option<f32> {
+        if self.games_played == 0 {
+            None
+        } else {
+            Some(self.games_won as f32 / self.games_played as f32 * 100.0)
+        }
+    }
+}
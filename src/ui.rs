@@ -1,9 +1,17 @@
-use ggez::{Context, GameResult};
+use ggez::{timer, Context, GameResult};
 use ggez::graphics::{self, Color, DrawParam, Text, DrawMode, Rect, MeshBuilder};
 use ggez::graphics::TextFragment;
-use std::time::Instant;
+use std::time::Duration;
 
-use crate::game_state::{MainState, ROUND_DURATION, WINDOW_WIDTH, WINDOW_HEIGHT, MAX_ROUNDS};
+use crate::game_state::{MainState, WINDOW_WIDTH, WINDOW_HEIGHT, LOW_HEALTH_PULSE, TIMER_TENTHS_BELOW};
+use crate::game_state::{GAME_OVER_STAT_REVEAL, ROUNDS_LEFT_WARNING};
+use crate::game_state::{end_turn_button_rect, nobody_donated, pet_swatch_rect, settings_button_rect, MENU_OPTION_COUNT, SETTINGS_LABEL_WIDTH};
+use crate::game_state::{pet_mine_button_rect, pet_revive_button_rect, pet_search_button_rect, pet_slot_y, pet_unlock_button_rect};
+use crate::game_state::{dead_bots_toggle_rect, donation_button_rect, opponent_row_rect, opponent_sort_rect, upgrade_button_rect, OpponentSort, CASH_IN_COST, DONATION_TIERS};
+use crate::settings::VolumeChannel;
+use crate::activity::ActivityKind;
+use crate::miner::{BotStrategy, UpgradeIcon, UpgradeKind};
+use crate::pet::{Pet, PetColor, MAX_PETS, PET_REVIVE_COST};
 
 // color palette
 const COLOR_BACKGROUND: Color = Color::new(0.95, 0.97, 1.0, 1.0);  // Light blue-gray
@@ -15,6 +23,10 @@ const COLOR_TEXT: Color = Color::new(0.2, 0.2, 0.25, 1.0);         // Dark slate
 const COLOR_TEXT_LIGHT: Color = Color::new(1.0, 1.0, 1.0, 1.0);    // White
 const COLOR_PANEL: Color = Color::new(1.0, 1.0, 1.0, 0.9);         // Slightly transparent white
 const COLOR_GOLD: Color = Color::new(0.85, 0.65, 0.2, 1.0);        // Gold
+const COLOR_BLOCKED: Color = Color::new(0.85, 0.15, 0.15, 1.0);    // Red flash for rejected actions
+
+// Keyboard focus ring thickness, in layout units (before the UI scale)
+const FOCUS_RING_WIDTH: f32 = 4.0;
 
 // Helper function to create modern looking panels
 fn draw_panel(
@@ -166,6 +178,23 @@ fn draw_button_with_text(
     Ok(())
 }
 
+// An upgrade's icon, `size` tall and centered on `center`
+fn draw_upgrade_icon(ctx: &mut Context, kind: UpgradeKind, center: [f32; 2], size: f32) -> GameResult {
+    let def = kind.def();
+    let mut builder = MeshBuilder::new();
+    match def.icon {
+        UpgradeIcon::Block => builder.rectangle(
+            DrawMode::fill(),
+            Rect::new(center[0] - size * 0.35, center[1] - size / 2.0, size * 0.7, size),
+            def.icon_color
+        )?,
+        UpgradeIcon::Disc => builder.circle(DrawMode::fill(), center, size * 0.375, 0.1, def.icon_color)?,
+    };
+
+    let icon = builder.build(ctx)?;
+    graphics::draw(ctx, &icon, DrawParam::default())
+}
+
 // Function to create a better looking header text
 fn draw_header_text(
     ctx: &mut Context,
@@ -264,7 +293,7 @@ fn draw_progress_bar(
     graphics::draw(ctx, &background, DrawParam::default())?;
     
     // Progress
-    let progress_width = rect.w * progress.min(1.0).max(0.0);
+    let progress_width = rect.w * progress.clamp(0.0, 1.0);
     if progress_width > 0.0 {
         let progress_rect = Rect::new(rect.x, rect.y, progress_width, rect.h);
         let progress_mesh = MeshBuilder::new()
@@ -282,48 +311,418 @@ fn draw_progress_bar(
     Ok(())
 }
 
+// Health bar that eases toward the real value. The filled part never shows more than
+// `health`; health just lost is drawn as a fading strip until the bar catches up.
+fn draw_health_bar(
+    ctx: &mut Context,
+    rect: Rect,
+    health: i32,
+    displayed_health: f32,
+    max_health: i32,
+    color: Color,
+) -> GameResult {
+    let max_health = max_health as f32;
+    let fill = displayed_health.min(health as f32);
+
+    draw_progress_bar(ctx, rect, fill / max_health, color)?;
+
+    if displayed_health > health as f32 {
+        let start = rect.w * (health as f32 / max_health).max(0.0);
+        let end = rect.w * (displayed_health / max_health).min(1.0);
+
+        let lost_rect = Rect::new(rect.x + start, rect.y, end - start, rect.h);
+        let lost_mesh = MeshBuilder::new()
+            .rectangle(
+                DrawMode::fill(),
+                lost_rect,
+                Color::new(0.95, 0.75, 0.7, 1.0) // Pale coral
+            )?
+            .build(ctx)?;
+
+        graphics::draw(ctx, &lost_mesh, DrawParam::default())?;
+    }
+
+    Ok(())
+}
+
+// Focus ring around whatever keyboard navigation has selected: a thick near-black ring
+// with a white one just inside it, so one of the two stands out whatever the colors
+// underneath are
+fn draw_focus_outline(ctx: &mut Context, rect: Rect) -> GameResult {
+    let dark_gap = 4.0 + FOCUS_RING_WIDTH / 2.0;
+    let light_gap = 3.0;
+
+    let outline = MeshBuilder::new()
+        .rounded_rectangle(
+            DrawMode::stroke(FOCUS_RING_WIDTH),
+            Rect::new(rect.x - dark_gap, rect.y - dark_gap, rect.w + dark_gap * 2.0, rect.h + dark_gap * 2.0),
+            6.0,
+            Color::new(0.05, 0.05, 0.1, 1.0)
+        )?
+        .rounded_rectangle(
+            DrawMode::stroke(2.0),
+            Rect::new(rect.x - light_gap, rect.y - light_gap, rect.w + light_gap * 2.0, rect.h + light_gap * 2.0),
+            5.0,
+            COLOR_TEXT_LIGHT
+        )?
+        .build(ctx)?;
+
+    graphics::draw(ctx, &outline, DrawParam::default())
+}
+
+// Faint highlight sweeping slowly across a panel, so the screen isn't completely still
+fn draw_header_shimmer(ctx: &mut Context, rect: Rect) -> GameResult {
+    const SWEEP_SECONDS: f32 = 8.0;
+    const BAND_WIDTH: f32 = 120.0;
+
+    let t = timer::time_since_start(ctx).as_secs_f32() % SWEEP_SECONDS / SWEEP_SECONDS;
+    let band_x = rect.x - BAND_WIDTH + t * (rect.w + BAND_WIDTH);
+
+    // Keep the band inside the panel
+    let left = band_x.max(rect.x);
+    let right = (band_x + BAND_WIDTH).min(rect.x + rect.w);
+    if right <= left {
+        return Ok(());
+    }
+
+    let band = MeshBuilder::new()
+        .rectangle(
+            DrawMode::fill(),
+            Rect::new(left, rect.y + 3.0, right - left, rect.h - 6.0),
+            Color::new(0.75, 0.85, 1.0, 0.18) // Pale blue, barely there
+        )?
+        .build(ctx)?;
+
+    graphics::draw(ctx, &band, DrawParam::default())
+}
+
+pub fn draw_menu_ui(state: &MainState, ctx: &mut Context) -> GameResult {
+    // Clear with the background color
+    graphics::clear(ctx, COLOR_BACKGROUND);
+
+    let panel_rect = state.menu_panel_rect();
+    draw_panel(ctx, panel_rect, COLOR_PANEL, 5.0)?;
+
+    draw_header_text(
+        ctx,
+        "Minefest",
+        panel_rect.x + panel_rect.w / 2.0 - 65.0,
+        panel_rect.y + 30.0,
+        36.0,
+        COLOR_GOLD
+    )?;
+
+    let subtitle_text = Text::new(
+        TextFragment::new("Out-mine and out-donate the bots to survive.")
+            .scale(16.0)
+            .color(COLOR_TEXT)
+    );
+
+    graphics::draw(
+        ctx,
+        &subtitle_text,
+        DrawParam::default().dest([panel_rect.x + 70.0, panel_rect.y + 85.0]),
+    )?;
+
+    // Lifetime record
+    let stats = &state.lifetime_stats;
+    let mut record = match stats.win_rate() {
+        Some(win_rate) => format!("Games played: {}   Win rate: {:.0}%", stats.games_played, win_rate),
+        None => "No games played yet".to_string(),
+    };
+    if stats.hardcore_played > 0 {
+        record.push_str(&format!("   Hardcore: {}/{} won", stats.hardcore_won, stats.hardcore_played));
+    }
+
+    let record_text = Text::new(
+        TextFragment::new(record)
+            .scale(16.0)
+            .color(COLOR_PRIMARY)
+    );
+
+    graphics::draw(
+        ctx,
+        &record_text,
+        DrawParam::default().dest([panel_rect.x + 70.0, panel_rect.y + 115.0]),
+    )?;
+
+    // How the pets have fared, once any game has had one
+    if stats.pets_lost + stats.pets_survived > 0 {
+        let pets_text = Text::new(
+            TextFragment::new(format!("Pets protected: {}   Pets lost: {}", stats.pets_survived, stats.pets_lost))
+                .scale(14.0)
+                .color(COLOR_DISABLED)
+        );
+
+        graphics::draw(
+            ctx,
+            &pets_text,
+            DrawParam::default().dest([panel_rect.x + 70.0, panel_rect.y + 136.0]),
+        )?;
+    }
+
+    // Game options with left/right arrows
+    for option in 0..MENU_OPTION_COUNT {
+        let (label, value) = match option {
+            0 => ("Bots", state.config.bot_count.to_string()),
+            1 => ("Difficulty", state.config.difficulty.label().to_string()),
+            2 => ("Round Length", format!("{}s", state.config.round_duration.as_secs())),
+            3 => ("Damage", state.config.damage_model.label().to_string()),
+            4 => {
+                let lock = state.config.donation_lock.as_secs();
+                ("Donation Lock", if lock == 0 { "Off".to_string() } else { format!("Last {}s", lock) })
+            },
+            5 => ("Adaptive Bots", if state.config.adaptive_difficulty { "On" } else { "Off" }.to_string()),
+            6 => ("Round 1 Shield", if state.config.first_round_shield { "On" } else { "Off" }.to_string()),
+            7 => ("Free Pet", if state.config.free_pet { "On" } else { "Off" }.to_string()),
+            8 => ("Practice Mode", if state.config.sandbox { "On" } else { "Off" }.to_string()),
+            9 => ("Turn-Based", if state.config.turn_based { "On" } else { "Off" }.to_string()),
+            10 => ("Bot Forecast", if state.config.bot_forecast { "On" } else { "Off" }.to_string()),
+            11 => ("Hardcore", if state.config.hardcore { "On" } else { "Off" }.to_string()),
+            12 => ("Health", format!("{} HP", state.config.starting_health)),
+            _ => ("Yield Curve", state.config.yield_curve.label().to_string()),
+        };
+
+        let x = state.menu_option_x(option);
+        let y = state.menu_option_y(option);
+
+        draw_header_text(ctx, label, x, y + 8.0, 18.0, COLOR_TEXT)?;
+
+        // What the chosen health means in play: last places it takes to be knocked out
+        if option == 12 {
+            let places = state.config.last_places_to_elimination();
+            let stakes_text = Text::new(
+                TextFragment::new(format!("Out after {} last place{}", places, if places == 1 { "" } else { "s" }))
+                    .scale(12.0)
+                    .color(COLOR_DISABLED)
+            );
+
+            graphics::draw(
+                ctx,
+                &stakes_text,
+                DrawParam::default().dest([x, y + 32.0]),
+            )?;
+        }
+
+        // The chosen curve's income from no upgrades up to both at the cap
+        if option == 13 {
+            let (lowest, highest) = state.config.yield_range();
+            let range_text = Text::new(
+                TextFragment::new(format!("{:.1}g/s at Lv0, {:.0}g/s at Lv{}", lowest, highest, state.config.upgrade_cap()))
+                    .scale(12.0)
+                    .color(COLOR_DISABLED)
+            );
+
+            graphics::draw(
+                ctx,
+                &range_text,
+                DrawParam::default().dest([x, y + 32.0]),
+            )?;
+        }
+
+        let (left_rect, right_rect) = state.menu_arrow_rects(option);
+        draw_button_with_text(ctx, left_rect, COLOR_PRIMARY, "<", 20.0, false)?;
+        draw_button_with_text(ctx, right_rect, COLOR_PRIMARY, ">", 20.0, false)?;
+
+        // Value centered between the arrows
+        let value_x = left_rect.x + left_rect.w + (right_rect.x - left_rect.x - left_rect.w) / 2.0
+            - value.len() as f32 * 5.0;
+
+        let value_text = Text::new(
+            TextFragment::new(value)
+                .scale(20.0)
+                .color(COLOR_PRIMARY)
+        );
+
+        graphics::draw(
+            ctx,
+            &value_text,
+            DrawParam::default().dest([value_x, y + 8.0]),
+        )?;
+    }
+
+    // Seed for the next game; left empty, a random one is used
+    let seed_rect = state.menu_seed_rect();
+    draw_header_text(ctx, "Seed", state.menu_option_x(0), seed_rect.y + 8.0, 18.0, COLOR_TEXT)?;
+
+    let seed_field = MeshBuilder::new()
+        .rectangle(DrawMode::fill(), seed_rect, COLOR_BACKGROUND)?
+        .rectangle(
+            DrawMode::stroke(2.0),
+            seed_rect,
+            if state.seed_input_focused { COLOR_ACCENT } else { COLOR_DISABLED }
+        )?
+        .build(ctx)?;
+
+    graphics::draw(ctx, &seed_field, DrawParam::default())?;
+
+    let seed_value = if state.seed_input_focused {
+        TextFragment::new(format!("{}|", state.seed_input)).color(COLOR_TEXT)
+    } else if state.seed_input.is_empty() {
+        TextFragment::new("Random").color(COLOR_DISABLED)
+    } else {
+        TextFragment::new(state.seed_input.clone()).color(COLOR_TEXT)
+    };
+
+    graphics::draw(
+        ctx,
+        &Text::new(seed_value.scale(18.0)),
+        DrawParam::default().dest([seed_rect.x + 10.0, seed_rect.y + 9.0]),
+    )?;
+
+    // Jump straight in on the default options
+    draw_button_with_text(
+        ctx,
+        state.menu_quick_match_rect(),
+        COLOR_GOLD,
+        "Quick Match",
+        22.0,
+        false
+    )?;
+
+    draw_button_with_text(
+        ctx,
+        state.menu_start_rect(),
+        COLOR_ACCENT,
+        "Start Game",
+        22.0,
+        false
+    )?;
+
+    // Resume the auto-saved game, if there is one
+    if state.save_available {
+        draw_button_with_text(
+            ctx,
+            state.menu_continue_rect(),
+            COLOR_PRIMARY,
+            "Continue",
+            20.0,
+            false
+        )?;
+    }
+
+    if let Some(focus_rect) = state.menu_focus_rect() {
+        draw_focus_outline(ctx, focus_rect)?;
+    }
+
+    Ok(())
+}
+
 pub fn draw_game_ui(state: &MainState, ctx: &mut Context) -> GameResult {
     // Clear with the background color
     graphics::clear(ctx, COLOR_BACKGROUND);
     
     // Calculate round timer progress
-    let round_elapsed = Instant::now().duration_since(state.round_start_time);
-    let time_left = if round_elapsed < ROUND_DURATION {
-        ROUND_DURATION - round_elapsed
-    } else {
-        std::time::Duration::from_secs(0)
-    };
-    let timer_progress = 1.0 - (time_left.as_secs_f32() / ROUND_DURATION.as_secs_f32());
+    let time_left = state.round_time_left();
+    let timer_progress = state.timer_bar_progress();
 
     // Top header panel
     let header_rect = Rect::new(10.0, 10.0, WINDOW_WIDTH - 20.0, 60.0);
     draw_panel(ctx, header_rect, COLOR_PANEL, 3.0)?;
+
+    if state.settings.animations {
+        draw_header_shimmer(ctx, header_rect)?;
+    }
     
-    // Draw round info
+    // Draw round info, warming up in color as the game nears its end
+    let rounds_remaining = state.rounds_remaining();
+    let round_color = if rounds_remaining == 0 {
+        COLOR_SECONDARY
+    } else if rounds_remaining <= ROUNDS_LEFT_WARNING {
+        Color::new(0.9, 0.6, 0.1, 1.0) // Orange
+    } else {
+        COLOR_PRIMARY
+    };
+
+    // On the final round the counter moves up to make room for the banner below it
+    let round_y = if rounds_remaining == 0 { 14.0 } else { 25.0 };
     draw_header_text(
         ctx,
-        &format!("Round {}/{}", state.current_round, MAX_ROUNDS),
+        &format!("Round {}/{}", state.current_round, state.config.round_count),
         30.0,
-        25.0,
+        round_y,
         24.0,
-        COLOR_PRIMARY
+        round_color
     )?;
+
+    if rounds_remaining == 0 {
+        draw_final_round_banner(state, ctx)?;
+    }
     
-    // Draw timer
-    let timer_rect = Rect::new(200.0, 30.0, 300.0, 20.0);
-    draw_progress_bar(ctx, timer_rect, timer_progress, COLOR_SECONDARY)?;
-    
-    // Draw time text
-    let time_text = Text::new(
-        TextFragment::new(format!("{}s", time_left.as_secs()))
-            .scale(18.0)
-            .color(COLOR_TEXT)
-    );
-    
-    graphics::draw(
+    if state.config.turn_based {
+        // No clock in turn-based games - the round lasts until the player ends the turn
+        draw_button_with_text(ctx, end_turn_button_rect(), COLOR_ACCENT, "End Turn (Enter)", 18.0, false)?;
+    } else {
+        // Draw timer
+        let timer_rect = Rect::new(200.0, 30.0, 300.0, 20.0);
+        draw_progress_bar(ctx, timer_rect, timer_progress, COLOR_SECONDARY)?;
+        
+        // Draw time text, with tenths over the final stretch so the last second doesn't sit on "0s"
+        let time_label = if time_left < TIMER_TENTHS_BELOW {
+            format!("{:.1}s", time_left.as_secs_f32())
+        } else {
+            format!("{}s", time_left.as_secs())
+        };
+        let time_text = Text::new(
+            TextFragment::new(time_label)
+                .scale(18.0)
+                .color(COLOR_TEXT)
+        );
+        
+        graphics::draw(
+            ctx,
+            &time_text,
+            DrawParam::default().dest([510.0, 28.0]),
+        )?;
+    }
+
+    // Bots have stopped donating for the rest of the round (or never donate, in practice mode)
+    if state.config.sandbox {
+        let practice_text = Text::new(
+            TextFragment::new("Practice mode")
+                .scale(18.0)
+                .color(COLOR_ACCENT)
+        );
+
+        graphics::draw(
+            ctx,
+            &practice_text,
+            DrawParam::default().dest([570.0, 28.0]),
+        )?;
+    } else if state.donations_locked() {
+        let locked_text = Text::new(
+            TextFragment::new("Donations locked")
+                .scale(18.0)
+                .color(COLOR_SECONDARY)
+        );
+
+        graphics::draw(
+            ctx,
+            &locked_text,
+            DrawParam::default().dest([570.0, 28.0]),
+        )?;
+    } else if state.config.hardcore {
+        let hardcore_text = Text::new(
+            TextFragment::new("Hardcore")
+                .scale(18.0)
+                .color(COLOR_SECONDARY)
+        );
+
+        graphics::draw(
+            ctx,
+            &hardcore_text,
+            DrawParam::default().dest([570.0, 28.0]),
+        )?;
+    }
+
+    // Settings button
+    draw_button_with_text(
         ctx,
-        &time_text,
-        DrawParam::default().dest([510.0, 28.0]),
+        settings_button_rect(),
+        COLOR_PRIMARY,
+        "Settings",
+        16.0,
+        false
     )?;
     
     // Player stats panel
@@ -334,16 +733,34 @@ pub fn draw_game_ui(state: &MainState, ctx: &mut Context) -> GameResult {
     draw_stat(
         ctx,
         "Gold: ",
-        &format!("{:.0}", state.player.gold),
+        &state.format_gold(state.player.gold),
         30.0,
         95.0,
         COLOR_GOLD
     )?;
+
+    // Where this round's gold has gone so far: upgrades versus donations. Counted as it's
+    // spent, so gold mined during the round doesn't skew either side.
+    let spending_text = Text::new(
+        TextFragment::new(format!(
+            "Upgrades {}g / Donated {}g",
+            state.format_gold(state.player.upgrade_spent),
+            state.format_gold(state.player.donated_gold)
+        ))
+            .scale(12.0)
+            .color(Color::new(0.45, 0.45, 0.5, 1.0))
+    );
+
+    graphics::draw(
+        ctx,
+        &spending_text,
+        DrawParam::default().dest([30.0, 114.0]),
+    )?;
     
     // Draw health
-    let health_color = if state.player.health <= 3 {
+    let health_color = if state.player.health <= state.player.critical_health() {
         COLOR_SECONDARY
-    } else if state.player.health <= 6 {
+    } else if state.player.health <= state.player.low_health() {
         Color::new(0.9, 0.6, 0.1, 1.0) // Orange
     } else {
         COLOR_ACCENT
@@ -358,12 +775,26 @@ pub fn draw_game_ui(state: &MainState, ctx: &mut Context) -> GameResult {
         health_color
     )?;
 
+    // Health bar under the number, same as the opponents get
+    let health_rect = Rect::new(30.0, 155.0, 200.0, 10.0);
+    draw_health_bar(
+        ctx,
+        health_rect,
+        state.player.health,
+        state.displayed_health(0),
+        state.player.max_health,
+        health_color
+    )?;
+
     draw_cursor_coordinates(state, ctx)?;
 
     // Draw upgrade options
     draw_upgrade_options(state, ctx)?;
     
-    draw_game_activity_log(state, ctx)?;
+    // Hidden log leaves its space empty; entries are still recorded
+    if state.settings.show_activity_log {
+        draw_game_activity_log(state, ctx)?;
+    }
 
     // Draw bot info
     draw_bot_info(state, ctx)?;
@@ -374,64 +805,275 @@ pub fn draw_game_ui(state: &MainState, ctx: &mut Context) -> GameResult {
     // Draw pet interface
     draw_pet_interface(state, ctx)?;
 
-    Ok(())
-}
+    draw_round_start_snapshot(state, ctx)?;
+
+    draw_low_health_vignette(state, ctx)?;
+
+    draw_cheat_flash(state, ctx)?;
+
+    // Tooltip goes last so it sits on top of everything
+    draw_tooltip(state, ctx)?;
 
-// Updated function with better contrast and visibility
-fn draw_cursor_coordinates(state: &MainState, ctx: &mut Context) -> GameResult {
-    if state.show_cursor_position {
-        // Create a fully opaque background with a bold color
-        let panel_rect = Rect::new(5.0, 5.0, 200.0, 40.0); // Positioned at top-left
-        
-        // Draw a bold border
-        let border_rect = Rect::new(
-            panel_rect.x - 2.0, 
-            panel_rect.y - 2.0, 
-            panel_rect.w + 4.0, 
-            panel_rect.h + 4.0
-        );
-        
-        let border = MeshBuilder::new()
-            .rounded_rectangle(
-                DrawMode::fill(),
-                border_rect,
-                8.0,
-                Color::new(1.0, 0.3, 0.3, 1.0) // Bright red border
-            )?
-            .build(ctx)?;
-        
-        graphics::draw(ctx, &border, DrawParam::default())?;
-        
-        // Main panel
-        let panel = MeshBuilder::new()
-            .rounded_rectangle(
-                DrawMode::fill(),
-                panel_rect,
-                6.0,
-                Color::new(0.0, 0.0, 0.0, 1.0) // Fully opaque black background
-            )?
-            .build(ctx)?;
-        
-        graphics::draw(ctx, &panel, DrawParam::default())?;
-        
-        // Draw the text with current coordinates
-        let position_text = Text::new(
-            TextFragment::new(format!("X: {:.0}, Y: {:.0}", state.cursor_position.0, state.cursor_position.1))
-                .scale(24.0) // Larger text
-                .color(Color::new(1.0, 1.0, 0.0, 1.0)) // Bright yellow text for maximum contrast
-        );
-        
-        graphics::draw(
-            ctx,
-            &position_text,
-            DrawParam::default().dest([panel_rect.x + 10.0, panel_rect.y + 8.0]),
-        )?;
-    }
-    
     Ok(())
 }
 
-fn draw_game_activity_log(state: &MainState, ctx: &mut Context) -> GameResult {
+// Red "Final Round!" banner under the round counter, pulsing if animations are on,
+// since surviving this round ends the game
+fn draw_final_round_banner(state: &MainState, ctx: &mut Context) -> GameResult {
+    let pulse = if state.settings.animations {
+        let t = timer::time_since_start(ctx).as_secs_f32() / LOW_HEALTH_PULSE.as_secs_f32();
+        0.8 + 0.2 * (t * std::f32::consts::TAU).sin()
+    } else {
+        1.0
+    };
+
+    let banner_rect = Rect::new(26.0, 42.0, 150.0, 24.0);
+    let banner = MeshBuilder::new()
+        .rounded_rectangle(
+            DrawMode::fill(),
+            banner_rect,
+            5.0,
+            Color::new(COLOR_SECONDARY.r, COLOR_SECONDARY.g, COLOR_SECONDARY.b, pulse)
+        )?
+        .build(ctx)?;
+
+    graphics::draw(ctx, &banner, DrawParam::default())?;
+
+    let banner_text = Text::new(
+        TextFragment::new("Final Round!")
+            .scale(18.0)
+            .color(COLOR_TEXT_LIGHT)
+    );
+
+    graphics::draw(
+        ctx,
+        &banner_text,
+        DrawParam::default().dest([banner_rect.x + 27.0, banner_rect.y + 3.0]),
+    )?;
+
+    Ok(())
+}
+
+// Confirmation that a cheat key fired, in its own purple banner so it can't be taken
+// for an activity log entry
+fn draw_cheat_flash(state: &MainState, ctx: &mut Context) -> GameResult {
+    let (text, alpha) = match state.cheat_flash_fade() {
+        Some(flash) => flash,
+        None => return Ok(()),
+    };
+
+    let banner_rect = Rect::new(WINDOW_WIDTH / 2.0 - 150.0, 76.0, 300.0, 36.0);
+    let banner = MeshBuilder::new()
+        .rounded_rectangle(
+            DrawMode::fill(),
+            banner_rect,
+            6.0,
+            Color::new(0.55, 0.2, 0.7, 0.9 * alpha)
+        )?
+        .build(ctx)?;
+
+    graphics::draw(ctx, &banner, DrawParam::default())?;
+
+    let flash_text = Text::new(
+        TextFragment::new(format!("Cheat: {}", text))
+            .scale(18.0)
+            .color(Color::new(1.0, 1.0, 1.0, alpha))
+    );
+
+    graphics::draw(
+        ctx,
+        &flash_text,
+        DrawParam::default().dest([banner_rect.x + 15.0, banner_rect.y + 9.0]),
+    )?;
+
+    Ok(())
+}
+
+// Everyone's gold and health as the round began, for the first few seconds of it
+fn draw_round_start_snapshot(state: &MainState, ctx: &mut Context) -> GameResult {
+    let (snapshot, alpha) = match state.round_start_snapshot_fade() {
+        Some(fade) => fade,
+        None => return Ok(()),
+    };
+
+    let faded = |color: Color| Color::new(color.r, color.g, color.b, color.a * alpha);
+
+    let panel_rect = Rect::new(150.0, 90.0, 420.0, 60.0 + snapshot.len() as f32 * 28.0);
+    // No shadow, since it wouldn't fade with the rest
+    draw_panel(ctx, panel_rect, Color::new(1.0, 1.0, 1.0, 0.95 * alpha), 0.0)?;
+
+    draw_header_text(
+        ctx,
+        &format!("Round {} - the field", state.current_round),
+        panel_rect.x + 20.0,
+        panel_rect.y + 12.0,
+        20.0,
+        faded(COLOR_PRIMARY)
+    )?;
+
+    let mut y_offset = panel_rect.y + 45.0;
+
+    for entry in snapshot {
+        let miner = if entry.miner_index == 0 { &state.player } else { &state.bots[entry.miner_index - 1] };
+        let (name, name_color) = (miner.display_name(), miner.color);
+
+        let columns = [
+            (name, name_color, 20.0),
+            (format!("{}g", state.format_gold(entry.gold)), COLOR_GOLD, 220.0),
+            (format!("{} HP", entry.health), if entry.health <= miner.critical_health() { COLOR_SECONDARY } else { COLOR_TEXT }, 330.0),
+        ];
+
+        for (text, color, x) in columns {
+            let column_text = Text::new(
+                TextFragment::new(text)
+                    .scale(18.0)
+                    .color(faded(color))
+            );
+
+            graphics::draw(
+                ctx,
+                &column_text,
+                DrawParam::default().dest([panel_rect.x + x, y_offset]),
+            )?;
+        }
+
+        y_offset += 28.0;
+    }
+
+    Ok(())
+}
+
+// Red bands around the screen edges while the player's health is critical. Pulses with time,
+// or holds steady with animations off.
+fn draw_low_health_vignette(state: &MainState, ctx: &mut Context) -> GameResult {
+    const BANDS: usize = 6;
+    const BAND_WIDTH: f32 = 8.0;
+
+    let strength = match state.low_health_vignette_strength() {
+        Some(strength) => strength,
+        None => return Ok(()),
+    };
+
+    let pulse = if state.settings.animations {
+        let t = timer::time_since_start(ctx).as_secs_f32() / LOW_HEALTH_PULSE.as_secs_f32();
+        0.6 + 0.4 * (t * std::f32::consts::TAU).sin()
+    } else {
+        0.8
+    };
+
+    // Outermost band is the most opaque, fading towards the middle of the screen
+    let mut vignette = MeshBuilder::new();
+    for band in 0..BANDS {
+        let inset = band as f32 * BAND_WIDTH + BAND_WIDTH / 2.0;
+        let alpha = 0.45 * strength * pulse * (1.0 - band as f32 / BANDS as f32);
+        vignette.rectangle(
+            DrawMode::stroke(BAND_WIDTH),
+            Rect::new(inset, inset, WINDOW_WIDTH - inset * 2.0, WINDOW_HEIGHT - inset * 2.0),
+            Color::new(COLOR_SECONDARY.r, COLOR_SECONDARY.g, COLOR_SECONDARY.b, alpha)
+        )?;
+    }
+
+    let vignette = vignette.build(ctx)?;
+    graphics::draw(ctx, &vignette, DrawParam::default())
+}
+
+// Draws the hovered element's tooltip next to the cursor once the hover delay has passed
+fn draw_tooltip(state: &MainState, ctx: &mut Context) -> GameResult {
+    let text = match &state.hover_tooltip {
+        Some(text) if state.tooltip_visible() => text.as_str(),
+        _ => return Ok(()),
+    };
+
+    let text_size = 14.0;
+    let longest_line = text.lines().map(|line| line.len()).max().unwrap_or(0);
+    let width = longest_line as f32 * (text_size * 0.5) + 20.0;
+    let height = text.lines().count() as f32 * (text_size + 4.0) + 14.0;
+
+    // Offset from the cursor, flipping to the other side near the right/bottom edges
+    let (cursor_x, cursor_y) = state.cursor_position;
+    let (screen_w, screen_h) = state.screen_size;
+    let mut x = cursor_x + 16.0;
+    let mut y = cursor_y + 16.0;
+    if x + width > screen_w {
+        x = cursor_x - width - 8.0;
+    }
+    if y + height > screen_h {
+        y = cursor_y - height - 8.0;
+    }
+
+    let tooltip_rect = Rect::new(x.max(0.0), y.max(0.0), width, height);
+    draw_panel(ctx, tooltip_rect, Color::new(0.15, 0.15, 0.2, 0.95), 2.0)?;
+
+    let tooltip_text = Text::new(
+        TextFragment::new(text)
+            .scale(text_size)
+            .color(COLOR_TEXT_LIGHT)
+    );
+
+    graphics::draw(
+        ctx,
+        &tooltip_text,
+        DrawParam::default().dest([tooltip_rect.x + 10.0, tooltip_rect.y + 7.0]),
+    )?;
+
+    Ok(())
+}
+
+// Updated function with better contrast and visibility
+fn draw_cursor_coordinates(state: &MainState, ctx: &mut Context) -> GameResult {
+    if state.show_cursor_position {
+        // Create a fully opaque background with a bold color
+        let panel_rect = Rect::new(5.0, 5.0, 200.0, 40.0); // Positioned at top-left
+        
+        // Draw a bold border
+        let border_rect = Rect::new(
+            panel_rect.x - 2.0, 
+            panel_rect.y - 2.0, 
+            panel_rect.w + 4.0, 
+            panel_rect.h + 4.0
+        );
+        
+        let border = MeshBuilder::new()
+            .rounded_rectangle(
+                DrawMode::fill(),
+                border_rect,
+                8.0,
+                Color::new(1.0, 0.3, 0.3, 1.0) // Bright red border
+            )?
+            .build(ctx)?;
+        
+        graphics::draw(ctx, &border, DrawParam::default())?;
+        
+        // Main panel
+        let panel = MeshBuilder::new()
+            .rounded_rectangle(
+                DrawMode::fill(),
+                panel_rect,
+                6.0,
+                Color::new(0.0, 0.0, 0.0, 1.0) // Fully opaque black background
+            )?
+            .build(ctx)?;
+        
+        graphics::draw(ctx, &panel, DrawParam::default())?;
+        
+        // Draw the text with current coordinates
+        let position_text = Text::new(
+            TextFragment::new(format!("X: {:.0}, Y: {:.0}", state.cursor_position.0, state.cursor_position.1))
+                .scale(24.0) // Larger text
+                .color(Color::new(1.0, 1.0, 0.0, 1.0)) // Bright yellow text for maximum contrast
+        );
+        
+        graphics::draw(
+            ctx,
+            &position_text,
+            DrawParam::default().dest([panel_rect.x + 10.0, panel_rect.y + 8.0]),
+        )?;
+    }
+    
+    Ok(())
+}
+
+fn draw_game_activity_log(state: &MainState, ctx: &mut Context) -> GameResult {
     // Center panel for game activity
     let log_rect = Rect::new(260.0, 80.0, WINDOW_WIDTH - 530.0, 240.0);
     draw_panel(ctx, log_rect, COLOR_PANEL, 3.0)?;
@@ -464,48 +1106,34 @@ fn draw_game_activity_log(state: &MainState, ctx: &mut Context) -> GameResult {
     
     graphics::draw(ctx, &line, DrawParam::default())?;
     
-    let mut activities = Vec::new();
-    
-    // Check for bot upgrade events (derived from their levels)
-    for (i, bot) in state.bots.iter().enumerate() {
-        if bot.alive {
-            // For demo purposes, create notifications based on bot levels
-            if bot.pickaxe_level >= 1 {
-                activities.push((format!("Bot #{} upgraded pickaxe to Lv{}", i + 1, bot.pickaxe_level), COLOR_SECONDARY));
-            }
-            
-            if bot.mine_level >= 1 {
-                activities.push((format!("Bot #{} upgraded mine to Lv{}", i + 1, bot.mine_level), COLOR_PRIMARY));
-            }
-        } else {
-            // Bot is dead
-            activities.push((format!("Bot #{} has died!", i + 1), COLOR_SECONDARY));
-        }
-    }
-    
-    // Add player notifications
-    if state.player.pickaxe_level > 0 {
-        activities.push((format!("You upgraded pickaxe to Lv{}", state.player.pickaxe_level), COLOR_ACCENT));
-    }
-    
-    if state.player.mine_level > 0 {
-        activities.push((format!("You upgraded mine to Lv{}", state.player.mine_level), COLOR_ACCENT));
-    }
-    
-    // Round started notification
-    activities.push((format!("Round {} started", state.current_round), COLOR_PRIMARY));
-    
-    // If we have too many activities, only show the most recent 5
-    if activities.len() > 5 {
-        activities = activities.into_iter().take(5).collect();
-    }
-    
-    // Reverse the activities to show newest at the top
-    activities.reverse();
+    // Most recent events first, optionally stamped with when in the round they happened
+    let activities: Vec<(String, Color, Option<Color>)> = state.activity_log
+        .recent(5)
+        .map(|entry| {
+            let color = match entry.kind {
+                ActivityKind::Round => COLOR_PRIMARY,
+                ActivityKind::Player => COLOR_ACCENT,
+                ActivityKind::Bot => COLOR_SECONDARY,
+                ActivityKind::Chatter => Color::new(0.45, 0.45, 0.5, 1.0), // Muted gray
+                ActivityKind::Death => COLOR_SECONDARY,
+            };
+            let message = if state.settings.activity_timestamps {
+                let secs = entry.round_time.as_secs();
+                format!("{}:{:02} {}", secs / 60, secs % 60, entry.message)
+            } else {
+                entry.message.clone()
+            };
+            // A bar in the miner's own color marks who the entry is about
+            let marker = entry.miner.map(|index| {
+                if index == 0 { state.player.color } else { state.bots[index - 1].color }
+            });
+            (message, color, marker)
+        })
+        .collect();
     
     let mut y_offset = log_rect.y + 60.0;
     
-    for (i, (message, color)) in activities.iter().enumerate() {
+    for (i, (message, color, marker)) in activities.iter().enumerate() {
         // Row background - alternating colors
         let row_rect = Rect::new(
             log_rect.x + 10.0,
@@ -530,6 +1158,18 @@ fn draw_game_activity_log(state: &MainState, ctx: &mut Context) -> GameResult {
             .build(ctx)?;
         
         graphics::draw(ctx, &row, DrawParam::default())?;
+
+        if let Some(marker) = marker {
+            let marker_bar = MeshBuilder::new()
+                .rectangle(
+                    DrawMode::fill(),
+                    Rect::new(row_rect.x + 3.0, row_rect.y + 5.0, 4.0, row_rect.h - 10.0),
+                    *marker
+                )?
+                .build(ctx)?;
+
+            graphics::draw(ctx, &marker_bar, DrawParam::default())?;
+        }
         
         // Activity text
         let activity_text = Text::new(
@@ -552,7 +1192,7 @@ fn draw_game_activity_log(state: &MainState, ctx: &mut Context) -> GameResult {
 
 fn draw_upgrade_options(state: &MainState, ctx: &mut Context) -> GameResult {
     // Upgrades panel
-    let upgrades_rect = Rect::new(10.0, 180.0, 240.0, 140.0);
+    let upgrades_rect = Rect::new(10.0, 180.0, 240.0, 40.0 + UpgradeKind::ALL.len() as f32 * 50.0);
     draw_panel(ctx, upgrades_rect, COLOR_PANEL, 3.0)?;
     
     // Panel header
@@ -564,123 +1204,89 @@ fn draw_upgrade_options(state: &MainState, ctx: &mut Context) -> GameResult {
         22.0,
         COLOR_PRIMARY
     )?;
-    
-    // Pickaxe upgrade button
-    let mut pickaxe_color = COLOR_SECONDARY;
-    let pickaxe_hover = false;
-    
-    if state.player.pickaxe_level < 4 && state.player.gold >= state.player.pickaxe_upgrade_cost() {
-        pickaxe_color = COLOR_ACCENT;
-    } else if state.player.pickaxe_level >= 4 {
-        pickaxe_color = COLOR_DISABLED;
-    }
-    
-    let pickaxe_rect = Rect::new(30.0, 220.0, 200.0, 40.0);
-    draw_button(ctx, pickaxe_rect, pickaxe_color, pickaxe_hover)?;
-    
-    // Pickaxe icon (simplified)
-    let pick_handle = Rect::new(45.0, 230.0, 15.0, 20.0);
-    let pick_handle_mesh = MeshBuilder::new()
-        .rectangle(
-            DrawMode::fill(),
-            pick_handle,
-            Color::new(0.6, 0.4, 0.2, 1.0) // Brown
-        )?
-        .build(ctx)?;
-    
-    graphics::draw(ctx, &pick_handle_mesh, DrawParam::default())?;
-    
-    // Text color based on button color
-    let text_color = if pickaxe_color.r + pickaxe_color.g + pickaxe_color.b > 1.8 {
-        COLOR_TEXT // Dark text for light buttons
-    } else {
-        COLOR_TEXT_LIGHT // Light text for dark buttons
-    };
-    
-    let pickaxe_text_str = if state.player.pickaxe_level >= 4 {
-        "Pickaxe Lv4/4: MAX"
-    } else {
-        &format!(
-            "Pickaxe Lv{}/4: {:.0}g",
-            state.player.pickaxe_level,
-            state.player.pickaxe_upgrade_cost()
-        )
-    };
-    
-    let pickaxe_text = Text::new(
-        TextFragment::new(pickaxe_text_str)
-        .scale(16.0) // Slightly smaller text (was 18.0)
-        .color(text_color)
+
+    // What the current levels mine per second on this game's yield curve
+    let income_text = Text::new(
+        TextFragment::new(format!("Income {:.1}g/s", state.player.income_per_second()))
+            .scale(14.0)
+            .color(COLOR_GOLD)
     );
-    
-    // Calculate better text position to ensure it fits in the button
-    let text_x = 70.0;
-    let text_y = 231.0; // Slight adjustment for vertical centering
-    
+
     graphics::draw(
         ctx,
-        &pickaxe_text,
-        DrawParam::default().dest([text_x, text_y]),
+        &income_text,
+        DrawParam::default().dest([135.0, 196.0]),
     )?;
     
-    // Mine upgrade button
-    let mut mine_color = COLOR_PRIMARY;
-    let mine_hover = false;
-    
-    if state.player.mine_level < 4 && state.player.gold >= state.player.mine_upgrade_cost() {
-        mine_color = COLOR_ACCENT;
-    } else if state.player.mine_level >= 4 {
-        mine_color = COLOR_DISABLED;
+    for (i, kind) in UpgradeKind::ALL.iter().enumerate() {
+        let kind = *kind;
+        let maxed = state.player.maxed(kind);
+
+        let mut button_color = kind.def().color;
+        
+        if !maxed && state.player.gold >= state.player.upgrade_cost(kind) {
+            button_color = COLOR_ACCENT;
+        } else if maxed {
+            button_color = COLOR_DISABLED;
+        }
+
+        // Flash red after a rejected click
+        if state.active_upgrade_feedback(kind).is_some() {
+            button_color = COLOR_BLOCKED;
+        }
+        
+        let button_rect = upgrade_button_rect(i);
+        draw_button(ctx, button_rect, button_color, false)?;
+        
+        draw_upgrade_icon(ctx, kind, [52.5, button_rect.y + 20.0], 20.0)?;
+        
+        // Text color based on button color
+        let text_color = if button_color.r + button_color.g + button_color.b > 1.8 {
+            COLOR_TEXT // Dark text for light buttons
+        } else {
+            COLOR_TEXT_LIGHT // Light text for dark buttons
+        };
+        
+        let button_text_str = if maxed {
+            format!("{} Lv{1}/{1}: MAX", kind.label(), state.player.max_level)
+        } else {
+            format!(
+                "{} Lv{}/{}: {:.0}g",
+                kind.label(),
+                state.player.level(kind),
+                state.player.max_level,
+                state.player.upgrade_cost(kind)
+            )
+        };
+        
+        let button_text = Text::new(
+            TextFragment::new(button_text_str)
+            .scale(16.0) // Slightly smaller text (was 18.0)
+            .color(text_color)
+        );
+        
+        // Slight adjustment for vertical centering
+        graphics::draw(
+            ctx,
+            &button_text,
+            DrawParam::default().dest([70.0, button_rect.y + 11.0]),
+        )?;
+    }
+
+    // Explain why the last upgrade click was rejected, next to the panel header
+    if let Some(feedback) = UpgradeKind::ALL.iter().find_map(|kind| state.active_upgrade_feedback(*kind)) {
+        let feedback_text = Text::new(
+            TextFragment::new(feedback.message)
+                .scale(14.0)
+                .color(COLOR_BLOCKED)
+        );
+
+        graphics::draw(
+            ctx,
+            &feedback_text,
+            DrawParam::default().dest([125.0, 195.0]),
+        )?;
     }
-    
-    let mine_rect = Rect::new(30.0, 270.0, 200.0, 40.0);
-    draw_button(ctx, mine_rect, mine_color, mine_hover)?;
-    
-    // Mine icon
-    let mine_icon = MeshBuilder::new()
-        .circle(
-            DrawMode::fill(),
-            [45.0 + 7.5, 280.0 + 7.5],
-            7.5,
-            0.1,
-            Color::new(0.5, 0.5, 0.5, 1.0) // Gray
-        )?
-        .build(ctx)?;
-    
-    graphics::draw(ctx, &mine_icon, DrawParam::default())?;
-    
-    // Text color based on button color
-    let text_color = if mine_color.r + mine_color.g + mine_color.b > 1.8 {
-        COLOR_TEXT // Dark text for light buttons
-    } else {
-        COLOR_TEXT_LIGHT // Light text for dark buttons
-    };
-    
-    let mine_text_str = if state.player.mine_level >= 4 {
-        "Mine Lv4/4: MAX"
-    } else {
-        &format!(
-            "Mine Lv{}/4: {:.0}g",
-            state.player.mine_level,
-            state.player.mine_upgrade_cost()
-        )
-    };
-    
-    let mine_text = Text::new(
-        TextFragment::new(mine_text_str)
-        .scale(16.0) // Slightly smaller text (was 18.0)
-        .color(text_color)
-    );
-    
-    // Calculate text position to ensure it fits in the button
-    let text_x = 70.0;
-    let text_y = 281.0;
-    
-    graphics::draw(
-        ctx,
-        &mine_text,
-        DrawParam::default().dest([text_x, text_y]),
-    )?;
 
     Ok(())
 }
@@ -700,121 +1306,192 @@ fn draw_bot_info(state: &MainState, ctx: &mut Context) -> GameResult {
         COLOR_PRIMARY
     )?;
     
+    // Sort headers, the active one highlighted
+    for (i, sort) in OpponentSort::ALL.iter().enumerate() {
+        let color = if state.opponent_sort == Some(*sort) { COLOR_ACCENT } else { COLOR_PRIMARY };
+        draw_button_with_text(ctx, opponent_sort_rect(i), color, sort.label(), 14.0, false)?;
+    }
+
+    let dead_label = if state.show_dead_bots { "Dead: Shown" } else { "Dead: Hidden" };
+    draw_button_with_text(ctx, dead_bots_toggle_rect(), COLOR_DISABLED, dead_label, 14.0, false)?;
+
     let mut y_offset = 380.0;
     
-    for (i, bot) in state.bots.iter().enumerate() {
-        if bot.alive {
-            // Background for bot row
-            let row_rect = Rect::new(20.0, y_offset - 5.0, opponents_rect.w - 20.0, 40.0);
-            let row_color = if i % 2 == 0 {
-                Color::new(0.95, 0.95, 0.95, 0.7) // Slightly darker for even rows
-            } else {
-                Color::new(1.0, 1.0, 1.0, 0.5) // Slightly lighter for odd rows
-            };
-            
-            let row = MeshBuilder::new()
+    for (row_index, i) in state.opponent_display_order().into_iter().enumerate() {
+        let bot = &state.bots[i];
+
+        // Background for bot row, which opens the bot's detail popup when clicked
+        let row_rect = opponent_row_rect(row_index);
+        let row_color = if !bot.alive {
+            Color::new(0.85, 0.85, 0.85, 0.7) // Greyed out for dead bots
+        } else if row_index % 2 == 0 {
+            Color::new(0.95, 0.95, 0.95, 0.7) // Slightly darker for even rows
+        } else {
+            Color::new(1.0, 1.0, 1.0, 0.5) // Slightly lighter for odd rows
+        };
+        
+        let row = MeshBuilder::new()
+            .rounded_rectangle(
+                DrawMode::fill(),
+                row_rect,
+                4.0,
+                row_color
+            )?
+            .build(ctx)?;
+        
+        graphics::draw(ctx, &row, DrawParam::default())?;
+
+        // The player's nemesis gets an outline in its color
+        if bot.strategy == Some(BotStrategy::Nemesis) && bot.alive {
+            let nemesis_outline = MeshBuilder::new()
                 .rounded_rectangle(
-                    DrawMode::fill(),
+                    DrawMode::stroke(2.0),
                     row_rect,
                     4.0,
-                    row_color
+                    bot.color
                 )?
                 .build(ctx)?;
-            
-            graphics::draw(ctx, &row, DrawParam::default())?;
-            
-            // Bot name with icon
-            let bot_name = Text::new(
-                TextFragment::new(format!("Bot #{}", i + 1))
-                    .scale(18.0)
-                    .color(COLOR_PRIMARY)
-            );
-            
-            graphics::draw(
-                ctx,
-                &bot_name,
-                DrawParam::default().dest([30.0, y_offset]),
-            )?;
-            
-            // Health bar
-            let health_rect = Rect::new(120.0, y_offset + 5.0, 100.0, 15.0);
-            let health_progress = bot.health as f32 / 10.0; // Assuming max health is 10
-            
-            // Health color based on remaining health
-            let health_color = if bot.health <= 3 {
-                COLOR_SECONDARY // Red for low health
-            } else if bot.health <= 6 {
-                Color::new(0.9, 0.6, 0.1, 1.0) // Orange for medium health
-            } else {
-                COLOR_ACCENT // Green for high health
-            };
-            
-            draw_progress_bar(ctx, health_rect, health_progress, health_color)?;
-            
-            // Health text
-            let health_text = Text::new(
-                TextFragment::new(format!("{}", bot.health))
-                    .scale(16.0)
-                    .color(COLOR_TEXT)
+
+            graphics::draw(ctx, &nemesis_outline, DrawParam::default())?;
+        }
+        
+        // Bot name with icon
+        let bot_name = Text::new(
+            TextFragment::new(bot.display_name())
+                .scale(18.0)
+                .color(if bot.alive { bot.color } else { COLOR_DISABLED })
+        );
+        
+        graphics::draw(
+            ctx,
+            &bot_name,
+            DrawParam::default().dest([30.0, y_offset]),
+        )?;
+
+        // Playstyle in small print under the name, if the player chose to see it
+        // (the nemesis always shows its, in its own color)
+        if let Some(strategy) = state.revealed_strategy(bot) {
+            let playstyle_color = if strategy == BotStrategy::Nemesis && bot.alive { strategy.color() } else { COLOR_DISABLED };
+            let playstyle_text = Text::new(
+                TextFragment::new(strategy.playstyle())
+                    .scale(11.0)
+                    .color(playstyle_color)
             );
-            
+
             graphics::draw(
                 ctx,
-                &health_text,
-                DrawParam::default().dest([230.0, y_offset]),
+                &playstyle_text,
+                DrawParam::default().dest([30.0, y_offset + 19.0]),
             )?;
-            
-            // Pickaxe level icon and text
-            let pickaxe_icon_rect = Rect::new(280.0, y_offset + 2.0, 10.0, 15.0);
-            let pickaxe_icon_mesh = MeshBuilder::new()
-                .rectangle(
-                    DrawMode::fill(),
-                    pickaxe_icon_rect,
-                    Color::new(0.6, 0.4, 0.2, 1.0) // Brown
-                )?
-                .build(ctx)?;
-            
-            graphics::draw(ctx, &pickaxe_icon_mesh, DrawParam::default())?;
-            
-            let pickaxe_text = Text::new(
-                TextFragment::new(format!("Lv{}", bot.pickaxe_level))
+        }
+        
+        // Health bar
+        let health_rect = Rect::new(120.0, y_offset + 5.0, 100.0, 15.0);
+        
+        // Health color based on remaining health
+        let health_color = if bot.health <= bot.critical_health() {
+            COLOR_SECONDARY // Red for low health
+        } else if bot.health <= bot.low_health() {
+            Color::new(0.9, 0.6, 0.1, 1.0) // Orange for medium health
+        } else {
+            COLOR_ACCENT // Green for high health
+        };
+        
+        draw_health_bar(
+            ctx,
+            health_rect,
+            bot.health,
+            state.displayed_health(i + 1),
+            bot.max_health,
+            health_color
+        )?;
+        
+        // Health text
+        let health_text = Text::new(
+            TextFragment::new(format!("{}", bot.health))
+                .scale(16.0)
+                .color(COLOR_TEXT)
+        );
+        
+        graphics::draw(
+            ctx,
+            &health_text,
+            DrawParam::default().dest([230.0, y_offset]),
+        )?;
+        
+        // Each upgrade's level with its icon, highlighted while the bot's just bought it
+        for (column, kind) in UpgradeKind::ALL.iter().enumerate() {
+            let column_x = 276.0 + column as f32 * 86.0;
+
+            if let Some(strength) = state.bot_upgrade_highlight(i, *kind) {
+                let highlight = MeshBuilder::new()
+                    .rounded_rectangle(
+                        DrawMode::fill(),
+                        Rect::new(column_x, y_offset - 2.0, 56.0, 24.0),
+                        4.0,
+                        Color::new(COLOR_GOLD.r, COLOR_GOLD.g, COLOR_GOLD.b, 0.6 * strength)
+                    )?
+                    .build(ctx)?;
+
+                graphics::draw(ctx, &highlight, DrawParam::default())?;
+            }
+
+            draw_upgrade_icon(ctx, *kind, [column_x + 9.0, y_offset + 10.0], 15.0)?;
+
+            let level_text = Text::new(
+                TextFragment::new(format!("Lv{}", bot.level(*kind)))
                     .scale(16.0)
-                    .color(COLOR_SECONDARY)
+                    .color(kind.def().color)
             );
-            
+
             graphics::draw(
                 ctx,
-                &pickaxe_text,
-                DrawParam::default().dest([300.0, y_offset]),
+                &level_text,
+                DrawParam::default().dest([column_x + 24.0, y_offset]),
             )?;
-            
-            // Mine level icon and text
-            let mine_icon = MeshBuilder::new()
-                .circle(
-                    DrawMode::fill(),
-                    [370.0, y_offset + 10.0],
-                    5.0,
-                    0.1,
-                    Color::new(0.5, 0.5, 0.5, 1.0) // Gray
-                )?
-                .build(ctx)?;
-            
-            graphics::draw(ctx, &mine_icon, DrawParam::default())?;
-            
-            let mine_text = Text::new(
-                TextFragment::new(format!("Lv{}", bot.mine_level))
-                    .scale(16.0)
-                    .color(COLOR_PRIMARY)
+        }
+
+        // Gold on hand and donated this round, the other two sort columns
+        let gold_text = Text::new(
+            TextFragment::new(format!("{}g", state.format_gold(bot.gold)))
+                .scale(16.0)
+                .color(COLOR_GOLD)
+        );
+
+        graphics::draw(
+            ctx,
+            &gold_text,
+            DrawParam::default().dest([430.0, y_offset]),
+        )?;
+
+        let donated_text = Text::new(
+            TextFragment::new(state.donation_label(bot.donated_gold))
+                .scale(16.0)
+                .color(COLOR_ACCENT)
+        );
+
+        graphics::draw(
+            ctx,
+            &donated_text,
+            DrawParam::default().dest([485.0, y_offset]),
+        )?;
+
+        // Faint estimate of what's still to come this round, with the Bot Forecast assist on
+        if let Some(projected) = state.projected_bot_donation(i) {
+            let forecast_text = Text::new(
+                TextFragment::new(format!("~+{}g", state.format_gold(projected)))
+                    .scale(11.0)
+                    .color(COLOR_DISABLED)
             );
-            
+
             graphics::draw(
                 ctx,
-                &mine_text,
-                DrawParam::default().dest([385.0, y_offset]),
+                &forecast_text,
+                DrawParam::default().dest([485.0, y_offset + 19.0]),
             )?;
-            
-            y_offset += 50.0;
         }
+        
+        y_offset += 50.0;
     }
     
     Ok(())
@@ -850,7 +1527,7 @@ fn draw_contribute_option(state: &MainState, ctx: &mut Context) -> GameResult {
     
     // Draw current donation
     let donated_text = Text::new(
-        TextFragment::new(format!("Current donation: {:.0}g", state.player.donated_gold))
+        TextFragment::new(format!("Current donation: {}g", state.format_gold(state.player.donated_gold)))
             .scale(18.0)
             .color(COLOR_GOLD)
     );
@@ -861,13 +1538,9 @@ fn draw_contribute_option(state: &MainState, ctx: &mut Context) -> GameResult {
         DrawParam::default().dest([WINDOW_WIDTH - 240.0, 150.0]),
     )?;
 
-    // Draw contribution amount buttons
-    let contribution_amounts = [10.0, 50.0, 100.0, 500.0, 1000.0];
-    let mut y_offset = 190.0;
-    
-    // Draw contribution options
-    for amount in &contribution_amounts {
-        let button_rect = Rect::new(WINDOW_WIDTH - 240.0, y_offset, 220.0, 30.0);
+    // Draw contribution options, labelled with their number key
+    for (i, amount) in DONATION_TIERS.iter().enumerate() {
+        let button_rect = donation_button_rect(i);
         
         let button_color = if state.player.gold >= *amount {
             COLOR_ACCENT
@@ -882,17 +1555,17 @@ fn draw_contribute_option(state: &MainState, ctx: &mut Context) -> GameResult {
             ctx,
             button_rect,
             button_color,
-            &format!("Donate {:.0}g", amount),
+            &format!("[{}] Donate {:.0}g", i + 1, amount),
             16.0,
             button_hover
         )?;
-        
-        y_offset += 40.0;
     }
     
     // Draw "All" option
-    let all_button_rect = Rect::new(WINDOW_WIDTH - 240.0, y_offset, 220.0, 30.0);
-    let all_button_color = if state.player.gold > 0.0 { 
+    let all_button_rect = donation_button_rect(DONATION_TIERS.len());
+    let all_button_color = if state.donate_all_armed() {
+        COLOR_SECONDARY // Waiting for the confirming press
+    } else if state.player.gold > 0.0 { 
         COLOR_GOLD
     } else { 
         COLOR_DISABLED
@@ -905,12 +1578,47 @@ fn draw_contribute_option(state: &MainState, ctx: &mut Context) -> GameResult {
         ctx,
         all_button_rect,
         all_button_color,
-        &format!("Donate All ({:.0}g)", state.player.gold),
+        &if state.donate_all_armed() {
+            format!("Confirm: donate all {}g?", state.format_gold(state.player.gold))
+        } else {
+            format!("[0] Donate All ({}g)", state.format_gold(state.player.gold))
+        },
         16.0,
         all_button_hover
     )?;
     
-    //draw_win_loss_tracker(state, ctx, WINDOW_WIDTH - 240.0, y_offset + 80.0)?;
+    //draw_win_loss_tracker(state, ctx, WINDOW_WIDTH - 240.0, all_button_rect.y + 80.0)?;
+
+    draw_gold_goal(state, ctx, all_button_rect.x, all_button_rect.y + 60.0)?;
+
+    Ok(())
+}
+
+// Progress toward the cheapest purchase the player can't afford yet
+fn draw_gold_goal(state: &MainState, ctx: &mut Context, x: f32, y: f32) -> GameResult {
+    draw_header_text(ctx, "Saving For", x, y, 20.0, COLOR_PRIMARY)?;
+
+    let (label, progress) = match state.next_gold_goal() {
+        Some((name, cost)) => (
+            format!("{} ({}/{}g)", name, state.format_gold(state.player.gold), state.format_gold(cost)),
+            state.player.gold / cost,
+        ),
+        None => ("All purchases available".to_string(), 1.0),
+    };
+
+    let goal_text = Text::new(
+        TextFragment::new(label)
+            .scale(16.0)
+            .color(COLOR_TEXT)
+    );
+
+    graphics::draw(
+        ctx,
+        &goal_text,
+        DrawParam::default().dest([x, y + 30.0]),
+    )?;
+
+    draw_progress_bar(ctx, Rect::new(x, y + 55.0, 220.0, 14.0), progress, COLOR_GOLD)?;
 
     Ok(())
 }
@@ -932,7 +1640,7 @@ pub fn draw_pet_interface(state: &MainState, ctx: &mut Context) -> GameResult {
         COLOR_PRIMARY
     )?;
     
-    if !state.pet.unlocked {
+    if state.pets.is_empty() {
         // Interface is locked - draw greyed out content with lock
         
         // Draw lock icon (simplified) - positioned relative to panel
@@ -976,15 +1684,9 @@ pub fn draw_pet_interface(state: &MainState, ctx: &mut Context) -> GameResult {
             DrawParam::default().dest([pet_rect.x + 20.0, pet_rect.y + 150.0]),
         )?;
         
-        // Unlock button - positioned relative to panel
-        let unlock_btn_rect = Rect::new(
-            pet_rect.x + 15.0, 
-            pet_rect.y + 250.0, 
-            pet_rect.w - 30.0, 
-            40.0
-        );
-        
-        let unlock_btn_color = if state.player.gold >= 1000.0 {
+        // Unlock button, priced by difficulty
+        let unlock_cost = state.pet_unlock_cost();
+        let unlock_btn_color = if state.player.gold >= unlock_cost {
             COLOR_ACCENT
         } else {
             COLOR_DISABLED
@@ -992,154 +1694,57 @@ pub fn draw_pet_interface(state: &MainState, ctx: &mut Context) -> GameResult {
         
         draw_button_with_text(
             ctx,
-            unlock_btn_rect,
+            pet_unlock_button_rect(0),
             unlock_btn_color,
-            "-Coming Soon-",
+            &format!("Unlock Pet ({:.0}g)", unlock_cost),
             18.0,
             false // Not hovered
         )?;
     } else {
-        // Pet is unlocked - draw interactive interface
-        
-        // Draw pet icon (simplified) - positioned relative to panel
-        let pet_circle = MeshBuilder::new()
-            .circle(
-                DrawMode::fill(),
-                [pet_rect.x + 40.0, pet_rect.y + 60.0],
-                15.0,
-                0.1,
-                if !state.pet.alive {
-                    COLOR_SECONDARY // Red if dead
-                } else if state.pet.mining {
-                    COLOR_ACCENT // Green if mining
-                } else if state.pet.searching {
-                    COLOR_GOLD // Gold if searching
-                } else {
-                    COLOR_PRIMARY // Blue if idle
-                }
-            )?
-            .build(ctx)?;
-        
-        graphics::draw(ctx, &pet_circle, DrawParam::default())?;
-        
-        // Draw pet status - positioned relative to panel
-        let status_text = if !state.pet.alive {
-            "Dead"
-        } else if state.pet.mining {
-            "Mining"
-        } else if state.pet.searching {
-            "Searching for Loot"
-        } else {
-            "Idle"
-        };
-        
-        let status_color = if !state.pet.alive {
-            COLOR_SECONDARY
-        } else if state.pet.mining {
-            COLOR_ACCENT
-        } else if state.pet.searching {
-            COLOR_GOLD
-        } else {
-            COLOR_TEXT
-        };
-        
-        draw_stat(
-            ctx,
-            "Status: ",
-            status_text,
-            pet_rect.x + 75.0,
-            pet_rect.y + 55.0,
-            status_color
-        )?;
-        
-        if state.pet.alive {
-            // Mining button - positioned relative to panel
-            let mine_btn_rect = Rect::new(
-                pet_rect.x + 15.0, 
-                pet_rect.y + 100.0, 
-                pet_rect.w - 30.0, 
-                40.0
-            );
-            
-            let mine_btn_color = if state.pet.mining {
-                COLOR_ACCENT // Green when active
-            } else {
-                COLOR_PRIMARY // Blue when inactive
-            };
-            
-            draw_button_with_text(
-                ctx,
-                mine_btn_rect,
-                mine_btn_color,
-                "Start/Stop Mining",
-                18.0,
-                false // Not hovered
-            )?;
-            
-            // Search button - positioned relative to panel
-            let search_btn_rect = Rect::new(
-                pet_rect.x + 15.0, 
-                pet_rect.y + 150.0, 
-                pet_rect.w - 30.0, 
-                40.0
-            );
-            
-            let search_btn_color = if state.pet.searching {
-                COLOR_GOLD // Gold when active
-            } else {
-                COLOR_PRIMARY // Blue when inactive
-            };
-            
-            draw_button_with_text(
-                ctx,
-                search_btn_rect,
-                search_btn_color,
-                "Start/Stop Searching",
-                18.0,
-                false // Not hovered
-            )?;
-            
-            // Take hit button - positioned relative to panel
-            let sacrifice_btn_rect = Rect::new(
-                pet_rect.x + 15.0, 
-                pet_rect.y + 200.0, 
-                pet_rect.w - 30.0, 
-                40.0
-            );
-            
+        // One slot per owned pet, oldest first
+        for (index, pet) in state.pets.iter().enumerate() {
+            draw_pet_slot(state, ctx, index, pet)?;
+        }
+
+        // Room for another pet
+        if state.pets.len() < MAX_PETS {
+            let unlock_cost = state.pet_unlock_cost();
             draw_button_with_text(
                 ctx,
-                sacrifice_btn_rect,
-                COLOR_SECONDARY,
-                "Use Pet to Take a Hit",
+                pet_unlock_button_rect(state.pets.len()),
+                if state.player.gold >= unlock_cost { COLOR_ACCENT } else { COLOR_DISABLED },
+                &format!("Another Pet ({:.0}g)", unlock_cost),
                 18.0,
-                false // Not hovered
+                false
             )?;
-            
-            // Info text - positioned relative to panel
-            let info_text = Text::new(
-                TextFragment::new("Your pet will automatically take\nthe next hit when you lose a round.")
+        }
+
+        // Info text - positioned relative to panel
+        let info_text = Text::new(
+            TextFragment::new("Your oldest live pet takes the\nnext hit when you lose a round.")
+                .scale(14.0)
+                .color(COLOR_TEXT)
+        );
+        
+        graphics::draw(
+            ctx,
+            &info_text,
+            DrawParam::default().dest([pet_rect.x + 20.0, pet_rect.y + 475.0]),
+        )?;
+
+        // Warn while the live standings put the player somewhere that takes damage,
+        // since a pet would absorb it
+        if state.pets.iter().any(|pet| pet.alive) && state.player_projected_damage() > 0 {
+            let warning_text = Text::new(
+                TextFragment::new("Your pet may take this hit")
                     .scale(14.0)
-                    .color(COLOR_TEXT)
-            );
-            
-            graphics::draw(
-                ctx,
-                &info_text,
-                DrawParam::default().dest([pet_rect.x + 20.0, pet_rect.y + 250.0]),
-            )?;
-        } else {
-            // Pet is dead - show sad message - positioned relative to panel
-            let dead_text = Text::new(
-                TextFragment::new("Your pet has sacrificed itself to\nprotect you. It can no longer help.\n\nUnlock a new pet in the next game.")
-                    .scale(16.0)
                     .color(COLOR_SECONDARY)
             );
-            
+
             graphics::draw(
                 ctx,
-                &dead_text,
-                DrawParam::default().dest([pet_rect.x + 20.0, pet_rect.y + 150.0]),
+                &warning_text,
+                DrawParam::default().dest([pet_rect.x + 20.0, pet_rect.y + 520.0]),
             )?;
         }
     }
@@ -1147,31 +1752,923 @@ pub fn draw_pet_interface(state: &MainState, ctx: &mut Context) -> GameResult {
     Ok(())
 }
 
-pub fn draw_round_end_ui(state: &MainState, ctx: &mut Context) -> GameResult {
-    // Clear with the background color
-    graphics::clear(ctx, COLOR_BACKGROUND);
+// Pet `index`'s slot in the pet panel: icon, status, ability buttons and color picker
+fn draw_pet_slot(state: &MainState, ctx: &mut Context, index: usize, pet: &Pet) -> GameResult {
+    let slot_y = pet_slot_y(index);
+    let icon_center = [840.0, slot_y + 20.0];
+
+    // Draw pet icon (simplified)
+    let pet_circle = MeshBuilder::new()
+        .circle(
+            DrawMode::fill(),
+            icon_center,
+            15.0,
+            0.1,
+            if !pet.alive {
+                COLOR_SECONDARY // Red if dead
+            } else if pet.mining {
+                COLOR_ACCENT // Green if mining
+            } else if pet.searching {
+                COLOR_GOLD // Gold if searching
+            } else {
+                pet_color(pet.color) // Chosen color if idle
+            }
+        )?
+        .build(ctx)?;
     
-    if let Some(results) = &state.round_results {
-        // Main panel
-        let panel_height = (results.len() as f32 * 40.0) + 150.0; // Increased panel height for button
-        let panel_rect = Rect::new(
-            WINDOW_WIDTH / 2.0 - 250.0,
-            WINDOW_HEIGHT / 2.0 - panel_height / 2.0,
-            500.0,
-            panel_height
+    graphics::draw(ctx, &pet_circle, DrawParam::default())?;
+
+    // Sparkle after a loot find: rays burst outwards and fade (a steady ring without animations)
+    if let Some(progress) = state.pet_loot_sparkle(index) {
+        let mut sparkle = MeshBuilder::new();
+
+        if state.settings.animations {
+            let alpha = 1.0 - progress;
+            let inner = 18.0 + progress * 10.0;
+            let outer = inner + 8.0;
+
+            for ray in 0..8 {
+                let angle = ray as f32 * std::f32::consts::FRAC_PI_4 + progress;
+                let (sin, cos) = angle.sin_cos();
+                sparkle.line(
+                    &[
+                        [icon_center[0] + cos * inner, icon_center[1] + sin * inner],
+                        [icon_center[0] + cos * outer, icon_center[1] + sin * outer],
+                    ],
+                    2.0,
+                    Color::new(COLOR_GOLD.r, COLOR_GOLD.g, COLOR_GOLD.b, alpha)
+                )?;
+            }
+        } else {
+            sparkle.circle(DrawMode::stroke(2.0), icon_center, 20.0, 0.1, COLOR_GOLD)?;
+        }
+
+        let sparkle = sparkle.build(ctx)?;
+        graphics::draw(ctx, &sparkle, DrawParam::default())?;
+    }
+    
+    // Draw pet status
+    let (status_text, status_color) = if !pet.alive {
+        ("Dead", COLOR_SECONDARY)
+    } else if pet.mining {
+        ("Mining", COLOR_ACCENT)
+    } else if pet.searching {
+        ("Searching for Loot", COLOR_GOLD)
+    } else {
+        ("Idle", COLOR_TEXT)
+    };
+    
+    draw_stat(
+        ctx,
+        "Status: ",
+        status_text,
+        875.0,
+        slot_y + 15.0,
+        status_color
+    )?;
+
+    // Fills up towards the next gold payout while mining, emptying again on each one
+    if let Some(progress) = state.pet_mine_progress(index) {
+        draw_progress_bar(ctx, Rect::new(875.0, slot_y + 37.0, 160.0, 6.0), progress, COLOR_GOLD)?;
+    }
+
+    if !pet.alive {
+        let dead_text = Text::new(
+            TextFragment::new("This pet sacrificed itself\nto protect you.")
+                .scale(16.0)
+                .color(COLOR_SECONDARY)
         );
         
-        draw_panel(ctx, panel_rect, COLOR_PANEL, 5.0)?;
-        
-        // Draw round results header
-        draw_header_text(
+        graphics::draw(
             ctx,
-            &format!("Round {} Results", state.current_round),
-            WINDOW_WIDTH / 2.0 - 120.0,
-            panel_rect.y + 20.0,
-            28.0,
-            COLOR_PRIMARY
+            &dead_text,
+            DrawParam::default().dest([820.0, slot_y + 50.0]),
         )?;
+
+        // Second chance, greyed out until it's affordable
+        draw_button_with_text(
+            ctx,
+            pet_revive_button_rect(index),
+            if state.can_revive_pet(index) { COLOR_ACCENT } else { COLOR_DISABLED },
+            &format!("Revive ({:.0}g)", PET_REVIVE_COST),
+            16.0,
+            false
+        )?;
+        return Ok(());
+    }
+    
+    // Mining and search buttons, lit up while active
+    draw_button_with_text(
+        ctx,
+        pet_mine_button_rect(index),
+        if pet.mining { COLOR_ACCENT } else { COLOR_PRIMARY },
+        "Mine",
+        16.0,
+        false // Not hovered
+    )?;
+    
+    draw_button_with_text(
+        ctx,
+        pet_search_button_rect(index),
+        if pet.searching { COLOR_GOLD } else { COLOR_PRIMARY },
+        "Search",
+        16.0,
+        false // Not hovered
+    )?;
+
+    // Color picker
+    for (i, color) in PetColor::ALL.iter().enumerate() {
+        let swatch_rect = pet_swatch_rect(index, i);
+
+        let swatch = MeshBuilder::new()
+            .rounded_rectangle(
+                DrawMode::fill(),
+                swatch_rect,
+                4.0,
+                pet_color(*color)
+            )?
+            .build(ctx)?;
+
+        graphics::draw(ctx, &swatch, DrawParam::default())?;
+
+        // Outline the current choice
+        if *color == pet.color {
+            let outline = MeshBuilder::new()
+                .rounded_rectangle(
+                    DrawMode::stroke(3.0),
+                    swatch_rect,
+                    4.0,
+                    COLOR_TEXT
+                )?
+                .build(ctx)?;
+
+            graphics::draw(ctx, &outline, DrawParam::default())?;
+        }
+    }
+
+    Ok(())
+}
+
+fn pet_color(color: PetColor) -> Color {
+    match color {
+        PetColor::Blue => COLOR_PRIMARY,
+        PetColor::Purple => Color::new(0.55, 0.35, 0.75, 1.0),
+        PetColor::Pink => Color::new(0.95, 0.5, 0.7, 1.0),
+        PetColor::Teal => Color::new(0.2, 0.65, 0.7, 1.0),
+        PetColor::Brown => Color::new(0.55, 0.4, 0.25, 1.0),
+    }
+}
+
+// S overlay: where everyone would place, and the damage they'd take, if the round ended now
+pub fn draw_standings_preview(state: &MainState, ctx: &mut Context) -> GameResult {
+    let standings = state.project_standings();
+
+    let panel_rect = Rect::new(150.0, 90.0, 420.0, 70.0 + standings.len() as f32 * 30.0);
+    draw_panel(ctx, panel_rect, Color::new(1.0, 1.0, 1.0, 0.97), 5.0)?;
+
+    draw_header_text(
+        ctx,
+        "If the round ended now",
+        panel_rect.x + 20.0,
+        panel_rect.y + 15.0,
+        20.0,
+        COLOR_PRIMARY
+    )?;
+
+    let mut y_offset = panel_rect.y + 50.0;
+
+    for (position, standing) in standings.iter().enumerate() {
+        let name = if standing.miner_index == 0 {
+            "You".to_string()
+        } else {
+            state.bots[standing.miner_index - 1].display_name()
+        };
+
+        let name_color = if standing.miner_index == 0 {
+            state.player.color
+        } else {
+            state.bots[standing.miner_index - 1].color
+        };
+
+        let row_text = Text::new(
+            TextFragment::new(format!("#{}  {}", position + 1, name))
+                .scale(18.0)
+                .color(name_color)
+        );
+
+        graphics::draw(
+            ctx,
+            &row_text,
+            DrawParam::default().dest([panel_rect.x + 20.0, y_offset]),
+        )?;
+
+        let gold_text = Text::new(
+            TextFragment::new(state.donation_label(standing.donated))
+                .scale(18.0)
+                .color(COLOR_GOLD)
+        );
+
+        graphics::draw(
+            ctx,
+            &gold_text,
+            DrawParam::default().dest([panel_rect.x + 240.0, y_offset]),
+        )?;
+
+        let damage_text = Text::new(
+            TextFragment::new(format!("-{}", standing.damage))
+                .scale(18.0)
+                .color(if standing.damage > 0 { COLOR_SECONDARY } else { COLOR_DISABLED })
+        );
+
+        graphics::draw(
+            ctx,
+            &damage_text,
+            DrawParam::default().dest([panel_rect.x + 350.0, y_offset]),
+        )?;
+
+        y_offset += 30.0;
+    }
+
+    Ok(())
+}
+
+// Popup with everything known about the bot picked in the opponents panel
+pub fn draw_bot_detail(state: &MainState, ctx: &mut Context) -> GameResult {
+    let bot = match state.selected_bot.and_then(|index| state.bots.get(index)) {
+        Some(bot) => bot,
+        None => return Ok(()),
+    };
+
+    // Dim the game underneath
+    let (screen_w, screen_h) = state.screen_size;
+    let backdrop = MeshBuilder::new()
+        .rectangle(
+            DrawMode::fill(),
+            Rect::new(0.0, 0.0, screen_w, screen_h),
+            Color::new(0.0, 0.0, 0.0, 0.4)
+        )?
+        .build(ctx)?;
+
+    graphics::draw(ctx, &backdrop, DrawParam::default())?;
+
+    let panel_rect = state.bot_detail_rect();
+    draw_panel(ctx, panel_rect, Color::new(1.0, 1.0, 1.0, 0.97), 5.0)?;
+
+    draw_header_text(
+        ctx,
+        &bot.display_name(),
+        panel_rect.x + 20.0,
+        panel_rect.y + 15.0,
+        22.0,
+        if bot.alive { bot.color } else { COLOR_DISABLED }
+    )?;
+
+    // Playstyle only if the player chose to see them (or it's the nemesis)
+    let playstyle = match state.revealed_strategy(bot) {
+        Some(strategy) => strategy.playstyle().to_string(),
+        None => "Playstyle hidden".to_string(),
+    };
+
+    let lines = [
+        (playstyle, COLOR_DISABLED),
+        (
+            if bot.alive { format!("Health: {}/{}", bot.health, bot.max_health) } else { "Out of the game".to_string() },
+            COLOR_TEXT
+        ),
+        (format!("Gold: {}g   Income: {:.1}g/s", state.format_gold(bot.gold), bot.income_per_second()), COLOR_GOLD),
+        (
+            UpgradeKind::ALL.iter().map(|kind| format!("{} Lv{}", kind.label(), bot.level(*kind))).collect::<Vec<_>>().join("   "),
+            COLOR_TEXT
+        ),
+        (format!("Upgrades bought: {}g", state.format_gold(bot.upgrade_investment())), COLOR_TEXT),
+        (
+            format!("Donated: {} this round, {}g in total", state.donation_label(bot.donated_gold), state.format_gold(bot.total_donated)),
+            COLOR_ACCENT
+        ),
+        (format!("Top donor in {} rounds, {} damage taken", bot.mvp_rounds, bot.total_damage_taken), COLOR_TEXT),
+    ];
+
+    let mut y_offset = panel_rect.y + 55.0;
+    for (line, color) in lines {
+        let line_text = Text::new(
+            TextFragment::new(line)
+                .scale(16.0)
+                .color(color)
+        );
+
+        graphics::draw(
+            ctx,
+            &line_text,
+            DrawParam::default().dest([panel_rect.x + 20.0, y_offset]),
+        )?;
+
+        y_offset += 24.0;
+    }
+
+    // Recent rounds, oldest first
+    draw_header_text(ctx, "Recent rounds", panel_rect.x + 20.0, y_offset + 6.0, 18.0, COLOR_PRIMARY)?;
+    y_offset += 32.0;
+
+    let rounds = bot.round_memory.rounds();
+    if rounds.is_empty() {
+        let none_text = Text::new(
+            TextFragment::new("No rounds played yet")
+                .scale(15.0)
+                .color(COLOR_DISABLED)
+        );
+
+        graphics::draw(
+            ctx,
+            &none_text,
+            DrawParam::default().dest([panel_rect.x + 20.0, y_offset]),
+        )?;
+    }
+
+    let first_round = state.current_round.saturating_sub(rounds.len());
+    for (i, (lost, donated)) in rounds.iter().enumerate() {
+        let round_text = Text::new(
+            TextFragment::new(format!(
+                "Round {}: donated {}g, {}",
+                first_round + i,
+                state.format_gold(*donated),
+                if *lost { "lost health" } else { "safe" }
+            ))
+                .scale(15.0)
+                .color(if *lost { COLOR_SECONDARY } else { COLOR_TEXT })
+        );
+
+        graphics::draw(
+            ctx,
+            &round_text,
+            DrawParam::default().dest([panel_rect.x + 20.0, y_offset]),
+        )?;
+
+        y_offset += 20.0;
+    }
+
+    let hint_text = Text::new(
+        TextFragment::new("Click outside to close")
+            .scale(13.0)
+            .color(COLOR_DISABLED)
+    );
+
+    graphics::draw(
+        ctx,
+        &hint_text,
+        DrawParam::default().dest([panel_rect.x + 20.0, panel_rect.y + panel_rect.h - 24.0]),
+    )?;
+
+    Ok(())
+}
+
+// Every miner side by side (player first, then the bots in line-up order), shown while Tab is held
+pub fn draw_comparison_table(state: &MainState, ctx: &mut Context) -> GameResult {
+    const ROW_HEIGHT: f32 = 32.0;
+    const COLUMNS: [(&str, f32); 7] = [
+        ("Miner", 0.0),
+        ("Health", 200.0),
+        ("Gold", 290.0),
+        ("Donated", 390.0),
+        ("Pickaxe", 500.0),
+        ("Mine", 590.0),
+        ("Income", 670.0),
+    ];
+
+    // Dim the game underneath
+    let (screen_w, screen_h) = state.screen_size;
+    let backdrop = MeshBuilder::new()
+        .rectangle(
+            DrawMode::fill(),
+            Rect::new(0.0, 0.0, screen_w, screen_h),
+            Color::new(0.0, 0.0, 0.0, 0.4)
+        )?
+        .build(ctx)?;
+
+    graphics::draw(ctx, &backdrop, DrawParam::default())?;
+
+    let miners: Vec<_> = std::iter::once(&state.player).chain(state.bots.iter()).collect();
+    let panel_w = 800.0;
+    let panel_h = 100.0 + miners.len() as f32 * ROW_HEIGHT;
+    let panel_rect = Rect::new(
+        (screen_w - panel_w) / 2.0,
+        ((screen_h - panel_h) / 2.0).max(10.0),
+        panel_w,
+        panel_h
+    );
+    draw_panel(ctx, panel_rect, Color::new(1.0, 1.0, 1.0, 0.97), 5.0)?;
+
+    draw_header_text(
+        ctx,
+        "Miner Comparison",
+        panel_rect.x + 20.0,
+        panel_rect.y + 15.0,
+        22.0,
+        COLOR_PRIMARY
+    )?;
+
+    let table_x = panel_rect.x + 30.0;
+    let header_y = panel_rect.y + 55.0;
+
+    for (title, offset) in COLUMNS.iter() {
+        let header = Text::new(
+            TextFragment::new(*title)
+                .scale(16.0)
+                .color(COLOR_DISABLED)
+        );
+
+        graphics::draw(
+            ctx,
+            &header,
+            DrawParam::default().dest([table_x + offset, header_y]),
+        )?;
+    }
+
+    let mut y_offset = header_y + 30.0;
+
+    for miner in miners.iter() {
+        // Dead miners stay in the table, greyed out
+        let row_color = if miner.alive { miner.color } else { COLOR_DISABLED };
+
+        let cells = [
+            miner.display_name(),
+            if miner.alive { format!("{}/{}", miner.health, miner.max_health) } else { "Out".to_string() },
+            format!("{}g", state.format_gold(miner.gold)),
+            state.donation_label(miner.donated_gold),
+            format!("Lv {}", miner.level(UpgradeKind::Pickaxe)),
+            format!("Lv {}", miner.level(UpgradeKind::Mine)),
+            format!("{:.1}g/s", miner.income_per_second()),
+        ];
+
+        for (cell, (_, offset)) in cells.into_iter().zip(COLUMNS.iter()) {
+            let cell_text = Text::new(
+                TextFragment::new(cell)
+                    .scale(18.0)
+                    .color(row_color)
+            );
+
+            graphics::draw(
+                ctx,
+                &cell_text,
+                DrawParam::default().dest([table_x + offset, y_offset]),
+            )?;
+        }
+
+        y_offset += ROW_HEIGHT;
+    }
+
+    Ok(())
+}
+
+// Shown in place of the normal screens after a frame fails to draw
+pub fn draw_error_screen(state: &MainState, ctx: &mut Context) -> GameResult {
+    graphics::clear(ctx, COLOR_BACKGROUND);
+
+    let panel_rect = state.error_panel_rect();
+    draw_panel(ctx, panel_rect, Color::new(1.0, 1.0, 1.0, 1.0), 5.0)?;
+
+    draw_header_text(
+        ctx,
+        "Something went wrong drawing the game",
+        panel_rect.x + 20.0,
+        panel_rect.y + 20.0,
+        22.0,
+        COLOR_SECONDARY
+    )?;
+
+    let message = state.draw_error.as_deref().unwrap_or("Unknown error");
+    let message_text = Text::new(
+        TextFragment::new(format!("{}\n\nContinue to try again, or quit.", message))
+            .scale(16.0)
+            .color(COLOR_TEXT)
+    );
+
+    graphics::draw(
+        ctx,
+        &message_text,
+        DrawParam::default().dest([panel_rect.x + 20.0, panel_rect.y + 65.0]),
+    )?;
+
+    draw_button_with_text(ctx, state.error_continue_rect(), COLOR_ACCENT, "Continue", 18.0, false)?;
+    draw_button_with_text(ctx, state.error_quit_rect(), COLOR_SECONDARY, "Quit", 18.0, false)?;
+
+    Ok(())
+}
+
+pub fn draw_settings_overlay(state: &MainState, ctx: &mut Context) -> GameResult {
+    // Dim whatever is underneath
+    let dim = MeshBuilder::new()
+        .rectangle(
+            DrawMode::fill(),
+            Rect::new(0.0, 0.0, state.screen_size.0, state.screen_size.1),
+            Color::new(0.0, 0.0, 0.0, 0.4)
+        )?
+        .build(ctx)?;
+
+    graphics::draw(ctx, &dim, DrawParam::default())?;
+
+    let panel_rect = state.settings_panel_rect();
+    draw_panel(ctx, panel_rect, Color::new(1.0, 1.0, 1.0, 1.0), 5.0)?;
+
+    draw_header_text(
+        ctx,
+        "Settings",
+        panel_rect.x + 20.0,
+        panel_rect.y + 20.0,
+        28.0,
+        COLOR_PRIMARY
+    )?;
+
+    // Volume sliders
+    for (i, channel) in VolumeChannel::ALL.iter().enumerate() {
+        let slider_rect = state.volume_slider_rect(i);
+        let volume = state.settings.volume(*channel);
+
+        let label_text = Text::new(
+            TextFragment::new(channel.label())
+                .scale(18.0)
+                .color(COLOR_TEXT)
+        );
+
+        graphics::draw(
+            ctx,
+            &label_text,
+            DrawParam::default().dest([panel_rect.x + 20.0, slider_rect.y - 3.0]),
+        )?;
+
+        draw_progress_bar(ctx, slider_rect, volume, COLOR_PRIMARY)?;
+
+        // Slider handle
+        let handle = MeshBuilder::new()
+            .circle(
+                DrawMode::fill(),
+                [slider_rect.x + slider_rect.w * volume, slider_rect.y + slider_rect.h / 2.0],
+                10.0,
+                0.1,
+                if state.dragging_slider == Some(*channel) { COLOR_ACCENT } else { COLOR_PRIMARY }
+            )?
+            .build(ctx)?;
+
+        graphics::draw(ctx, &handle, DrawParam::default())?;
+
+        let percent_text = Text::new(
+            TextFragment::new(format!("{:.0}%", volume * 100.0))
+                .scale(14.0)
+                .color(COLOR_TEXT)
+        );
+
+        graphics::draw(
+            ctx,
+            &percent_text,
+            DrawParam::default().dest([slider_rect.x + slider_rect.w / 2.0 - 12.0, slider_rect.y + 18.0]),
+        )?;
+    }
+
+    // Animations toggle
+    let animations_label = Text::new(
+        TextFragment::new("Animations")
+            .scale(18.0)
+            .color(COLOR_TEXT)
+    );
+
+    let toggle_rect = state.animations_toggle_rect();
+    graphics::draw(
+        ctx,
+        &animations_label,
+        DrawParam::default().dest([toggle_rect.x - SETTINGS_LABEL_WIDTH, toggle_rect.y + 5.0]),
+    )?;
+
+    let (toggle_color, toggle_text) = if state.settings.animations {
+        (COLOR_PRIMARY, "On")
+    } else {
+        (COLOR_DISABLED, "Off")
+    };
+    draw_button_with_text(ctx, toggle_rect, toggle_color, toggle_text, 16.0, false)?;
+
+    // Layout preset
+    let layout_label = Text::new(
+        TextFragment::new("Layout")
+            .scale(18.0)
+            .color(COLOR_TEXT)
+    );
+
+    let layout_rect = state.layout_toggle_rect();
+    graphics::draw(
+        ctx,
+        &layout_label,
+        DrawParam::default().dest([layout_rect.x - SETTINGS_LABEL_WIDTH, layout_rect.y + 5.0]),
+    )?;
+
+    draw_button_with_text(ctx, layout_rect, COLOR_PRIMARY, state.settings.layout.label(), 16.0, false)?;
+
+    // Auto-upgrade assistant and the gold it keeps back
+    let auto_upgrade_label = Text::new(
+        TextFragment::new("Auto-Upgrade")
+            .scale(18.0)
+            .color(COLOR_TEXT)
+    );
+
+    let auto_upgrade_rect = state.auto_upgrade_toggle_rect();
+    graphics::draw(
+        ctx,
+        &auto_upgrade_label,
+        DrawParam::default().dest([auto_upgrade_rect.x - SETTINGS_LABEL_WIDTH, auto_upgrade_rect.y + 5.0]),
+    )?;
+
+    let (auto_upgrade_color, auto_upgrade_text) = if state.settings.auto_upgrade {
+        (COLOR_PRIMARY, "On")
+    } else {
+        (COLOR_DISABLED, "Off")
+    };
+    draw_button_with_text(ctx, auto_upgrade_rect, auto_upgrade_color, auto_upgrade_text, 16.0, false)?;
+
+    let reserve_label = Text::new(
+        TextFragment::new("Keep for Donating")
+            .scale(18.0)
+            .color(COLOR_TEXT)
+    );
+
+    let reserve_rect = state.auto_upgrade_reserve_rect();
+    graphics::draw(
+        ctx,
+        &reserve_label,
+        DrawParam::default().dest([reserve_rect.x - SETTINGS_LABEL_WIDTH, reserve_rect.y + 5.0]),
+    )?;
+
+    draw_button_with_text(
+        ctx,
+        reserve_rect,
+        if state.settings.auto_upgrade { COLOR_PRIMARY } else { COLOR_DISABLED },
+        &format!("{:.0}g", state.settings.auto_upgrade_reserve),
+        16.0,
+        false
+    )?;
+
+    // Gold precision
+    let decimals_label = Text::new(
+        TextFragment::new("Gold Decimals")
+            .scale(18.0)
+            .color(COLOR_TEXT)
+    );
+
+    let decimals_rect = state.gold_decimals_rect();
+    graphics::draw(
+        ctx,
+        &decimals_label,
+        DrawParam::default().dest([decimals_rect.x - SETTINGS_LABEL_WIDTH, decimals_rect.y + 5.0]),
+    )?;
+
+    let decimals_text = match state.settings.gold_decimals {
+        0 => "Whole".to_string(),
+        decimals => format!("{} places", decimals),
+    };
+    draw_button_with_text(ctx, decimals_rect, COLOR_PRIMARY, &decimals_text, 16.0, false)?;
+
+    // Low-health flashing, for players sensitive to it
+    let vignette_label = Text::new(
+        TextFragment::new("Low Health Pulse")
+            .scale(18.0)
+            .color(COLOR_TEXT)
+    );
+
+    let vignette_rect = state.low_health_vignette_rect();
+    graphics::draw(
+        ctx,
+        &vignette_label,
+        DrawParam::default().dest([vignette_rect.x - SETTINGS_LABEL_WIDTH, vignette_rect.y + 5.0]),
+    )?;
+
+    let (vignette_color, vignette_text) = if state.settings.low_health_vignette {
+        (COLOR_PRIMARY, "On")
+    } else {
+        (COLOR_DISABLED, "Off")
+    };
+    draw_button_with_text(ctx, vignette_rect, vignette_color, vignette_text, 16.0, false)?;
+
+    // Round time in front of activity log entries
+    let timestamps_label = Text::new(
+        TextFragment::new("Log Timestamps")
+            .scale(18.0)
+            .color(COLOR_TEXT)
+    );
+
+    let timestamps_rect = state.activity_timestamps_rect();
+    graphics::draw(
+        ctx,
+        &timestamps_label,
+        DrawParam::default().dest([timestamps_rect.x - SETTINGS_LABEL_WIDTH, timestamps_rect.y + 5.0]),
+    )?;
+
+    let (timestamps_color, timestamps_text) = if state.settings.activity_timestamps {
+        (COLOR_PRIMARY, "On")
+    } else {
+        (COLOR_DISABLED, "Off")
+    };
+    draw_button_with_text(ctx, timestamps_rect, timestamps_color, timestamps_text, 16.0, false)?;
+
+    // Bot playstyles in the opponents panel (hidden is the harder way to play)
+    let strategies_label = Text::new(
+        TextFragment::new("Show Bot Styles")
+            .scale(18.0)
+            .color(COLOR_TEXT)
+    );
+
+    let strategies_rect = state.reveal_strategies_rect();
+    graphics::draw(
+        ctx,
+        &strategies_label,
+        DrawParam::default().dest([strategies_rect.x - SETTINGS_LABEL_WIDTH, strategies_rect.y + 5.0]),
+    )?;
+
+    let (strategies_color, strategies_text) = if state.settings.reveal_strategies {
+        (COLOR_PRIMARY, "On")
+    } else {
+        (COLOR_DISABLED, "Off")
+    };
+    draw_button_with_text(ctx, strategies_rect, strategies_color, strategies_text, 16.0, false)?;
+
+    // The Game Activity panel during play (also toggled with L)
+    let activity_log_label = Text::new(
+        TextFragment::new("Game Activity Log")
+            .scale(18.0)
+            .color(COLOR_TEXT)
+    );
+
+    let activity_log_rect = state.activity_log_toggle_rect();
+    graphics::draw(
+        ctx,
+        &activity_log_label,
+        DrawParam::default().dest([activity_log_rect.x - SETTINGS_LABEL_WIDTH, activity_log_rect.y + 5.0]),
+    )?;
+
+    let (activity_log_color, activity_log_text) = if state.settings.show_activity_log {
+        (COLOR_PRIMARY, "On")
+    } else {
+        (COLOR_DISABLED, "Off")
+    };
+    draw_button_with_text(ctx, activity_log_rect, activity_log_color, activity_log_text, 16.0, false)?;
+
+    // Frame rate limit; switching vsync on or off only applies after a restart
+    let frame_cap_label = Text::new(
+        TextFragment::new("Frame Rate")
+            .scale(18.0)
+            .color(COLOR_TEXT)
+    );
+
+    let frame_cap_rect = state.frame_cap_rect();
+    graphics::draw(
+        ctx,
+        &frame_cap_label,
+        DrawParam::default().dest([frame_cap_rect.x - SETTINGS_LABEL_WIDTH, frame_cap_rect.y + 5.0]),
+    )?;
+
+    let frame_cap_text = if state.frame_cap_needs_restart() {
+        format!("{} (restart)", state.settings.frame_cap.label())
+    } else {
+        state.settings.frame_cap.label().to_string()
+    };
+    draw_button_with_text(ctx, frame_cap_rect, COLOR_PRIMARY, &frame_cap_text, 16.0, false)?;
+
+    // Wipes the lifetime stats, after a confirming second press
+    let (reset_color, reset_text) = if state.reset_stats_armed() {
+        (COLOR_SECONDARY, "Confirm Reset?")
+    } else {
+        (COLOR_DISABLED, "Reset Stats")
+    };
+    draw_button_with_text(ctx, state.reset_stats_rect(), reset_color, reset_text, 18.0, false)?;
+
+    // Abandons the current run, after a confirming second press
+    if state.can_quit_to_menu() {
+        let (quit_color, quit_text) = if state.quit_to_menu_armed() && state.config.hardcore {
+            (COLOR_SECONDARY, "Click again to forfeit (a loss)")
+        } else if state.quit_to_menu_armed() {
+            (COLOR_SECONDARY, "Click again to abandon this game")
+        } else {
+            (COLOR_PRIMARY, "Quit to Menu")
+        };
+        draw_button_with_text(ctx, state.quit_to_menu_rect(), quit_color, quit_text, 18.0, false)?;
+    }
+
+    draw_button_with_text(
+        ctx,
+        state.settings_close_rect(),
+        COLOR_ACCENT,
+        "Close (Esc)",
+        18.0,
+        false
+    )?;
+
+    if let Some(focus_rect) = state.settings_focus_rect() {
+        draw_focus_outline(ctx, focus_rect)?;
+    }
+
+    Ok(())
+}
+
+pub fn draw_round_end_ui(state: &MainState, ctx: &mut Context) -> GameResult {
+    // Clear with the background color
+    graphics::clear(ctx, COLOR_BACKGROUND);
+    let (center_x, center_y) = state.screen_center();
+    
+    if let Some(results) = &state.round_results {
+        // Main panel
+        let panel_height = state.round_end_panel_height(); // Room for the gold choice and button
+        let panel_rect = Rect::new(
+            center_x - 250.0,
+            center_y - panel_height / 2.0,
+            500.0,
+            panel_height
+        );
+        
+        draw_panel(ctx, panel_rect, COLOR_PANEL, 5.0)?;
+
+        // Personal recap toast just above the panel for the first couple of seconds
+        if let Some((recap, alpha)) = state.round_recap_fade() {
+            let toast_rect = Rect::new(panel_rect.x + 20.0, panel_rect.y - 50.0, panel_rect.w - 40.0, 38.0);
+            let toast = MeshBuilder::new()
+                .rounded_rectangle(
+                    DrawMode::fill(),
+                    toast_rect,
+                    6.0,
+                    Color::new(COLOR_PRIMARY.r, COLOR_PRIMARY.g, COLOR_PRIMARY.b, alpha)
+                )?
+                .build(ctx)?;
+
+            graphics::draw(ctx, &toast, DrawParam::default())?;
+
+            let recap_text = Text::new(
+                TextFragment::new(recap)
+                    .scale(18.0)
+                    .color(Color::new(1.0, 1.0, 1.0, alpha))
+            );
+
+            graphics::draw(
+                ctx,
+                &recap_text,
+                DrawParam::default().dest([toast_rect.x + 15.0, toast_rect.y + 10.0]),
+            )?;
+        }
+        
+        // Draw round results header
+        draw_header_text(
+            ctx,
+            &format!("Round {} Results", state.current_round),
+            center_x - 120.0,
+            panel_rect.y + 20.0,
+            28.0,
+            COLOR_PRIMARY
+        )?;
+
+        // The shield note takes the spot of the other callouts below
+        let shielded = state.first_round_shielded();
+        if shielded {
+            let shield_text = Text::new(
+                TextFragment::new("First round: no damage")
+                    .scale(16.0)
+                    .color(COLOR_ACCENT)
+            );
+
+            graphics::draw(
+                ctx,
+                &shield_text,
+                DrawParam::default().dest([panel_rect.x + 20.0, panel_rect.y + 50.0]),
+            )?;
+        }
+
+        // Call out the closest race between neighbouring places
+        let closest_call = state.closest_call();
+        if let Some((position, gap)) = closest_call.filter(|_| !shielded) {
+            let photo_finish_text = Text::new(
+                TextFragment::new(format!(
+                    "Photo finish! #{} and #{} were {}g apart",
+                    position + 1,
+                    position + 2,
+                    state.format_gold(gap)
+                ))
+                .scale(16.0)
+                .color(COLOR_GOLD)
+            );
+
+            graphics::draw(
+                ctx,
+                &photo_finish_text,
+                DrawParam::default().dest([panel_rect.x + 20.0, panel_rect.y + 50.0]),
+            )?;
+        }
+
+        let empty_round = nobody_donated(results);
+        if empty_round && !shielded {
+            let empty_round_text = Text::new(
+                TextFragment::new("Nobody donated - no one takes damage this round")
+                    .scale(16.0)
+                    .color(COLOR_SECONDARY)
+            );
+
+            graphics::draw(
+                ctx,
+                &empty_round_text,
+                DrawParam::default().dest([panel_rect.x + 20.0, panel_rect.y + 50.0]),
+            )?;
+        }
         
         let mut y_offset = panel_rect.y + 70.0;
         
@@ -1213,7 +2710,9 @@ pub fn draw_round_end_ui(state: &MainState, ctx: &mut Context) -> GameResult {
                 30.0
             );
             
-            let row_color = if position % 2 == 0 {
+            let row_color = if *miner_index == 0 {
+                Color::new(0.85, 0.95, 0.87, 0.9) // Pale green so the player finds their row
+            } else if position % 2 == 0 {
                 Color::new(0.95, 0.95, 0.95, 0.7) // Slightly darker for even rows
             } else {
                 Color::new(1.0, 1.0, 1.0, 0.5) // Slightly lighter for odd rows
@@ -1229,6 +2728,36 @@ pub fn draw_round_end_ui(state: &MainState, ctx: &mut Context) -> GameResult {
                 .build(ctx)?;
             
             graphics::draw(ctx, &row, DrawParam::default())?;
+
+            // Player's row also gets a green outline (the photo finish outline goes on top)
+            if *miner_index == 0 {
+                let player_outline = MeshBuilder::new()
+                    .rounded_rectangle(
+                        DrawMode::stroke(2.0),
+                        row_rect,
+                        4.0,
+                        COLOR_ACCENT
+                    )?
+                    .build(ctx)?;
+
+                graphics::draw(ctx, &player_outline, DrawParam::default())?;
+            }
+
+            // Outline the two rows in the photo finish
+            if let Some((close_position, _)) = closest_call
+                && (position == close_position || position == close_position + 1)
+            {
+                let outline = MeshBuilder::new()
+                    .rounded_rectangle(
+                        DrawMode::stroke(2.0),
+                        row_rect,
+                        4.0,
+                        COLOR_GOLD
+                    )?
+                    .build(ctx)?;
+
+                graphics::draw(ctx, &outline, DrawParam::default())?;
+            }
             
             // Position/rank
             let position_color = match position {
@@ -1250,17 +2779,18 @@ pub fn draw_round_end_ui(state: &MainState, ctx: &mut Context) -> GameResult {
                 DrawParam::default().dest([panel_rect.x + 25.0, y_offset]),
             )?;
             
-            // Player name
-            let miner_name = if *miner_index == 0 {
-                "You (Player)".to_string()
+            // Player name, in their identity color
+            let (miner_name, name_color) = if *miner_index == 0 {
+                ("You (Player)".to_string(), state.player.color)
             } else {
-                format!("Bot #{}", miner_index)
+                let bot = &state.bots[*miner_index - 1];
+                (bot.display_name(), bot.color)
             };
             
             let name_text = Text::new(
                 TextFragment::new(miner_name)
                     .scale(18.0)
-                    .color(COLOR_TEXT)
+                    .color(name_color)
             );
             
             graphics::draw(
@@ -1268,10 +2798,37 @@ pub fn draw_round_end_ui(state: &MainState, ctx: &mut Context) -> GameResult {
                 &name_text,
                 DrawParam::default().dest([panel_rect.x + 70.0, y_offset]),
             )?;
+
+            // MVP badge for the top donor
+            if position == 0 && !empty_round {
+                let badge_rect = Rect::new(panel_rect.x + 175.0, y_offset, 38.0, 20.0);
+                let badge = MeshBuilder::new()
+                    .rounded_rectangle(
+                        DrawMode::fill(),
+                        badge_rect,
+                        4.0,
+                        COLOR_GOLD
+                    )?
+                    .build(ctx)?;
+
+                graphics::draw(ctx, &badge, DrawParam::default())?;
+
+                let badge_text = Text::new(
+                    TextFragment::new("MVP")
+                        .scale(13.0)
+                        .color(COLOR_TEXT_LIGHT)
+                );
+
+                graphics::draw(
+                    ctx,
+                    &badge_text,
+                    DrawParam::default().dest([badge_rect.x + 7.0, badge_rect.y + 4.0]),
+                )?;
+            }
             
             // Donated gold
             let gold_text = Text::new(
-                TextFragment::new(format!("{:.0}g", donated_gold))
+                TextFragment::new(format!("{}g", state.format_gold(*donated_gold)))
                     .scale(18.0)
                     .color(COLOR_GOLD)
             );
@@ -1282,8 +2839,12 @@ pub fn draw_round_end_ui(state: &MainState, ctx: &mut Context) -> GameResult {
                 DrawParam::default().dest([panel_rect.x + 220.0, y_offset]),
             )?;
             
-            // Damage taken
-            let damage = position as i32;
+            // Damage taken under the chosen damage model
+            let damage = if empty_round || shielded {
+                0
+            } else {
+                state.config.damage_model.damage_for(position, results.len())
+            };
             
             let damage_text = Text::new(
                 TextFragment::new(format!("-{}", damage))
@@ -1296,13 +2857,76 @@ pub fn draw_round_end_ui(state: &MainState, ctx: &mut Context) -> GameResult {
                 &damage_text,
                 DrawParam::default().dest([panel_rect.x + 370.0, y_offset]),
             )?;
+
+            // Movement since last round; a neutral marker in the first round
+            let (change_label, change_color) = match state.rank_change(*miner_index, position) {
+                Some(change) if change > 0 => (format!("+{}", change), COLOR_ACCENT),
+                Some(change) if change < 0 => (format!("{}", change), COLOR_SECONDARY),
+                Some(_) => ("=".to_string(), COLOR_DISABLED),
+                None => ("--".to_string(), COLOR_DISABLED),
+            };
+
+            let change_text = Text::new(
+                TextFragment::new(change_label)
+                    .scale(16.0)
+                    .color(change_color)
+            );
+
+            graphics::draw(
+                ctx,
+                &change_text,
+                DrawParam::default().dest([panel_rect.x + 430.0, y_offset + 1.0]),
+            )?;
             
             y_offset += 40.0;
         }
+
+        // One-time refund for an upgrade bought too late to pay off
+        if let Some(upgrade) = state.undoable_upgrade() {
+            draw_button_with_text(
+                ctx,
+                state.round_end_undo_rect(),
+                COLOR_SECONDARY,
+                &format!(
+                    "Undo last-second {} upgrade (refund {}g)",
+                    upgrade.kind.label().to_lowercase(),
+                    state.format_gold(state.player.top_level_cost(upgrade.kind))
+                ),
+                16.0,
+                false
+            )?;
+        }
+
+        // What to do with unspent gold: carry it all over (default) or cash some in for health
+        let keep_color = if state.cash_in_selected { COLOR_DISABLED } else { COLOR_PRIMARY };
+        draw_button_with_text(
+            ctx,
+            state.round_end_keep_rect(),
+            keep_color,
+            &format!("Keep {}g", state.format_gold(state.player.gold)),
+            16.0,
+            false
+        )?;
+
+        let cash_in_color = if !state.can_cash_in() {
+            COLOR_DISABLED
+        } else if state.cash_in_selected {
+            COLOR_PRIMARY
+        } else {
+            Color::new(COLOR_PRIMARY.r, COLOR_PRIMARY.g, COLOR_PRIMARY.b, 0.45)
+        };
+        draw_button_with_text(
+            ctx,
+            state.round_end_cash_in_rect(),
+            cash_in_color,
+            &format!("Cash {:.0}g for +1 HP", CASH_IN_COST),
+            16.0,
+            false
+        )?;
         
         // Draw continue button
         let button_rect = Rect::new(
-            WINDOW_WIDTH / 2.0 - 125.0,
+            center_x - 125.0,
             panel_rect.y + panel_height - 60.0, // Position at the bottom with some padding
             250.0, 
             40.0
@@ -1316,24 +2940,139 @@ pub fn draw_round_end_ui(state: &MainState, ctx: &mut Context) -> GameResult {
             18.0,
             false // Not hovered
         )?;
+
+        // Damage breakdown for the hovered row
+        draw_tooltip(state, ctx)?;
+    } else {
+        // No results to show - still give the player a way forward.
+        // Same layout as a results panel with no rows, which the click handler relies on.
+        let panel_height = 150.0;
+        let panel_rect = Rect::new(
+            center_x - 250.0,
+            center_y - panel_height / 2.0,
+            500.0,
+            panel_height
+        );
+
+        draw_panel(ctx, panel_rect, COLOR_PANEL, 5.0)?;
+
+        draw_header_text(
+            ctx,
+            "Loading results...",
+            panel_rect.x + 20.0,
+            panel_rect.y + 20.0,
+            24.0,
+            COLOR_PRIMARY
+        )?;
+
+        let button_rect = Rect::new(
+            center_x - 125.0,
+            panel_rect.y + panel_height - 60.0,
+            250.0,
+            40.0
+        );
+
+        draw_button_with_text(
+            ctx,
+            button_rect,
+            COLOR_ACCENT,
+            "Continue to Next Round",
+            18.0,
+            false
+        )?;
     }
     
     Ok(())
 }
 
-pub fn draw_game_over_ui(state: &MainState, ctx: &mut Context) -> GameResult {
-    // Clear with the background color
-    graphics::clear(ctx, COLOR_BACKGROUND);
-    
-    // Check if player won
-    let player_won = state.player_has_won();
+// The player outlasted every bot: confetti, then the final stats revealed one by one
+pub fn draw_victory_ui(state: &MainState, ctx: &mut Context) -> GameResult {
+    graphics::clear(ctx, Color::new(0.97, 0.93, 0.78, 1.0)); // Warm gold
+
+    let elapsed = state.game_over_elapsed();
+    draw_confetti(state, ctx, elapsed)?;
+
+    // Without animations everything shows at once
+    let reveal = if state.settings.animations { Some(elapsed) } else { None };
+    draw_game_over_summary(state, ctx, COLOR_ACCENT, "Victory! You have won!", reveal)
+}
+
+// Died or ran out of rounds: a muted backdrop and the summary straight away
+pub fn draw_defeat_ui(state: &MainState, ctx: &mut Context) -> GameResult {
+    graphics::clear(ctx, Color::new(0.28, 0.29, 0.33, 1.0)); // Slate
+
+    let message = if state.player.alive { "Game Over - Time's Up" } else { "Game Over - You Died!" };
+    draw_game_over_summary(state, ctx, COLOR_SECONDARY, message, None)
+}
+
+// Falling confetti over the victory screen; a fixed scatter without animations
+fn draw_confetti(state: &MainState, ctx: &mut Context, elapsed: Duration) -> GameResult {
+    const PIECES: usize = 80;
+    const COLORS: [Color; 4] = [COLOR_ACCENT, COLOR_GOLD, COLOR_SECONDARY, COLOR_PRIMARY];
+
+    let (screen_w, screen_h) = state.screen_size;
+    let t = if state.settings.animations { elapsed.as_secs_f32() } else { 0.0 };
+
+    let mut confetti = MeshBuilder::new();
+    for i in 0..PIECES {
+        // Cheap per-piece spread so every piece has its own column, speed and sway
+        let seed = (i as f32 * 12.9898).sin() * 43758.547;
+        let spread = seed - seed.floor();
+        let x = (i as f32 / PIECES as f32 + spread * 0.1) * screen_w + (t * 2.0 + i as f32).sin() * 15.0;
+        let fall_speed = 60.0 + spread * 90.0;
+        let y = (spread * screen_h + t * fall_speed) % (screen_h + 20.0) - 10.0;
+
+        confetti.rectangle(
+            DrawMode::fill(),
+            Rect::new(x, y, 6.0, 10.0),
+            COLORS[i % COLORS.len()]
+        )?;
+    }
+
+    let confetti = confetti.build(ctx)?;
+    graphics::draw(ctx, &confetti, DrawParam::default())
+}
+
+// The final stats shared by both end screens, as (label, value, value color) rows
+fn game_over_stat_rows(state: &MainState) -> Vec<(&'static str, String, Color)> {
+    let rounds_completed = if state.player.alive { state.current_round } else { state.current_round - 1 };
+    let wins_count = state.past_results.iter().filter(|&&win| win).count();
+
+    // Win streak the game ended on
+    let best_streak = state.past_results.iter().rev().take_while(|&&win| win).count();
+
+    vec![
+        ("Rounds: ", format!("{}/{}", rounds_completed, state.config.round_count), COLOR_PRIMARY),
+        (
+            "Final Health: ",
+            state.player.health.to_string(),
+            if state.player.health > state.player.low_health() { COLOR_ACCENT } else { COLOR_SECONDARY }
+        ),
+        (
+            "Damage Taken: ",
+            state.player.total_damage_taken.to_string(),
+            if state.player.total_damage_taken > 0 { COLOR_SECONDARY } else { COLOR_ACCENT }
+        ),
+        ("Gold Collected: ", format!("{}g", state.format_gold(state.player.total_gold_mined)), COLOR_GOLD),
+        ("Rounds Won: ", format!("{}/{}", wins_count, state.past_results.len()), COLOR_ACCENT),
+        ("Win Streak: ", best_streak.to_string(), COLOR_ACCENT),
+        ("Total Donated: ", format!("{}g", state.format_gold(state.player.total_donated)), COLOR_GOLD),
+        ("MVP Rounds: ", state.player.mvp_rounds.to_string(), COLOR_GOLD),
+    ]
+}
+
+// Summary panel, buttons and seed for either end screen. With `reveal` set, each stat row
+// fades in GAME_OVER_STAT_REVEAL after the one above it, counting from the game's end.
+fn draw_game_over_summary(
+    state: &MainState,
+    ctx: &mut Context,
+    header_bar_color: Color,
+    game_over_message: &str,
+    reveal: Option<Duration>,
+) -> GameResult {
+    let (center_x, _) = state.screen_center();
     
-    let panel_rect = Rect::new(
-        WINDOW_WIDTH / 2.0 - 250.0,
-        WINDOW_HEIGHT / 2.0 - 200.0, // Make panel taller
-        500.0,
-        400.0 // Increased height
-    );
+    let panel_rect = state.game_over_panel_rect();
     
     draw_panel(ctx, panel_rect, COLOR_PANEL, 8.0)?;
     
@@ -1345,12 +3084,6 @@ pub fn draw_game_over_ui(state: &MainState, ctx: &mut Context) -> GameResult {
         50.0
     );
     
-    let header_bar_color = if player_won {
-        COLOR_ACCENT // Green for victory
-    } else {
-        COLOR_SECONDARY // Red for defeat
-    };
-    
     let header_bar = MeshBuilder::new()
         .rounded_rectangle(
             DrawMode::fill(),
@@ -1363,16 +3096,10 @@ pub fn draw_game_over_ui(state: &MainState, ctx: &mut Context) -> GameResult {
     graphics::draw(ctx, &header_bar, DrawParam::default())?;
     
     // Draw game over text
-    let game_over_message = if player_won {
-        "Victory! You have won!"
-    } else {
-        "Game Over - You Died!"
-    };
-
     draw_header_text(
         ctx,
         game_over_message,
-        WINDOW_WIDTH / 2.0 - 180.0,
+        center_x - 180.0,
         panel_rect.y + 10.0,
         28.0,
         COLOR_TEXT_LIGHT
@@ -1396,152 +3123,284 @@ pub fn draw_game_over_ui(state: &MainState, ctx: &mut Context) -> GameResult {
     
     graphics::draw(ctx, &line, DrawParam::default())?;
     
-    // Game stats
-    let stats_text = Text::new(
-        TextFragment::new(format!("Rounds Completed: {}/{}", 
-            if state.player.alive { state.current_round } else { state.current_round - 1 }, 
-            MAX_ROUNDS
-        ))
-        .scale(20.0)
-        .color(COLOR_PRIMARY)
+    // Game stats, label then value
+    for (i, (label, value, value_color)) in game_over_stat_rows(state).into_iter().enumerate() {
+        let alpha = match reveal {
+            Some(elapsed) => {
+                let shown_for = elapsed.as_secs_f32() - GAME_OVER_STAT_REVEAL.as_secs_f32() * (i + 1) as f32;
+                (shown_for / GAME_OVER_STAT_REVEAL.as_secs_f32()).clamp(0.0, 1.0)
+            },
+            None => 1.0,
+        };
+        if alpha <= 0.0 {
+            continue;
+        }
+
+        let y = panel_rect.y + 90.0 + i as f32 * 34.0;
+        let faded = |color: Color| Color::new(color.r, color.g, color.b, color.a * alpha);
+
+        let label_text = Text::new(
+            TextFragment::new(label)
+                .scale(20.0)
+                .color(faded(COLOR_TEXT))
+        );
+
+        graphics::draw(
+            ctx,
+            &label_text,
+            DrawParam::default().dest([panel_rect.x + 100.0, y]),
+        )?;
+
+        let value_text = Text::new(
+            TextFragment::new(value)
+                .scale(20.0)
+                .color(faded(value_color))
+        );
+
+        graphics::draw(
+            ctx,
+            &value_text,
+            DrawParam::default().dest([panel_rect.x + 260.0, y]),
+        )?;
+    }
+
+    // Time played and donation efficiency, top of the free space right of the stats
+    let time_label = Text::new(
+        TextFragment::new("Time played")
+            .scale(16.0)
+            .color(COLOR_PRIMARY)
     );
-    
+
     graphics::draw(
         ctx,
-        &stats_text,
-        DrawParam::default().dest([panel_rect.x + 100.0, panel_rect.y + 90.0]),
+        &time_label,
+        DrawParam::default().dest([panel_rect.x + 340.0, panel_rect.y + 90.0]),
     )?;
-    
-    // Add player health
-    let health_label = Text::new(
-        TextFragment::new("Final Health: ")
-            .scale(20.0)
+
+    let played_secs = state.time_played.as_secs();
+    let time_value = Text::new(
+        TextFragment::new(format!("{}:{:02}", played_secs / 60, played_secs % 60))
+            .scale(16.0)
             .color(COLOR_TEXT)
     );
-    
+
     graphics::draw(
         ctx,
-        &health_label,
-        DrawParam::default().dest([panel_rect.x + 100.0, panel_rect.y + 130.0]),
+        &time_value,
+        DrawParam::default().dest([panel_rect.x + 340.0, panel_rect.y + 112.0]),
     )?;
-    
-    let health_value = Text::new(
-        TextFragment::new(format!("{}", state.player.health))
-            .scale(20.0)
-            .color(if state.player.health > 5 { COLOR_ACCENT } else { COLOR_SECONDARY })
+
+    let efficiency_label = Text::new(
+        TextFragment::new("Donation efficiency")
+            .scale(16.0)
+            .color(COLOR_PRIMARY)
     );
-    
+
     graphics::draw(
         ctx,
-        &health_value,
-        DrawParam::default().dest([panel_rect.x + 260.0, panel_rect.y + 130.0]),
+        &efficiency_label,
+        DrawParam::default().dest([panel_rect.x + 340.0, panel_rect.y + 136.0]),
     )?;
-    
-    // Gold collected stat
-    let gold_label = Text::new(
-        TextFragment::new("Gold Collected: ")
-            .scale(20.0)
+
+    let efficiency = match state.donation_efficiency() {
+        Some(gold_per_damage) => format!("{:.1}g / HP saved", gold_per_damage),
+        None => "n/a (none avoided)".to_string(),
+    };
+    let efficiency_value = Text::new(
+        TextFragment::new(efficiency)
+            .scale(16.0)
             .color(COLOR_TEXT)
     );
-    
+
     graphics::draw(
         ctx,
-        &gold_label,
-        DrawParam::default().dest([panel_rect.x + 100.0, panel_rect.y + 170.0]),
+        &efficiency_value,
+        DrawParam::default().dest([panel_rect.x + 340.0, panel_rect.y + 158.0]),
     )?;
-    
-    let gold_value = Text::new(
-        TextFragment::new(format!("{:.0}g", state.player.total_gold_mined))
-            .scale(20.0)
-            .color(COLOR_GOLD)
+
+    // What the pets contributed, in the free space right of the stats (only if there ever was one)
+    if !state.pets.is_empty() {
+        let pet_x = panel_rect.x + 340.0;
+
+        let pet_header = Text::new(
+            TextFragment::new("Pet contribution")
+                .scale(16.0)
+                .color(COLOR_PRIMARY)
+        );
+
+        graphics::draw(
+            ctx,
+            &pet_header,
+            DrawParam::default().dest([pet_x, panel_rect.y + 192.0]),
+        )?;
+
+        let pet_lines = [
+            format!("Mined: {}g", state.format_gold(state.pets.iter().map(|pet| pet.gold_mined).sum())),
+            format!("Loot: {}g", state.format_gold(state.pets.iter().map(|pet| pet.loot_found).sum())),
+            format!("Hits taken: {}", state.pets.iter().map(|pet| pet.hits_absorbed).sum::<usize>()),
+        ];
+
+        for (i, line) in pet_lines.into_iter().enumerate() {
+            let line_text = Text::new(
+                TextFragment::new(line)
+                    .scale(16.0)
+                    .color(COLOR_TEXT)
+            );
+
+            graphics::draw(
+                ctx,
+                &line_text,
+                DrawParam::default().dest([pet_x, panel_rect.y + 226.0 + i as f32 * 34.0]),
+            )?;
+        }
+    }
+
+    // Who went out, in order
+    let elimination = if state.elimination_order.is_empty() {
+        "No eliminations".to_string()
+    } else {
+        let names: Vec<String> = state.elimination_order
+            .iter()
+            .map(|&index| if index == 0 { "You".to_string() } else { state.bots[index - 1].display_name() })
+            .collect();
+
+        match state.first_blood_round {
+            Some(round) => format!("Out: {}  (first blood round {})", names.join(", "), round),
+            None => format!("Out: {}", names.join(", ")),
+        }
+    };
+
+    let elimination_text = Text::new(
+        TextFragment::new(elimination)
+            .scale(15.0)
+            .color(COLOR_SECONDARY)
     );
-    
+
     graphics::draw(
         ctx,
-        &gold_value,
-        DrawParam::default().dest([panel_rect.x + 260.0, panel_rect.y + 170.0]),
+        &elimination_text,
+        DrawParam::default().dest([panel_rect.x + 30.0, panel_rect.y + 368.0]),
     )?;
     
-    // Add round wins count
-    let wins_count = state.past_results.iter().filter(|&&win| win).count();
-    
-    let wins_label = Text::new(
-        TextFragment::new("Rounds Won: ")
-            .scale(20.0)
-            .color(COLOR_TEXT)
-    );
-    
-    graphics::draw(
+    // Draw restart button (keeps the current settings), unless a lost hardcore run rules it out
+    if !state.restart_locked() {
+        draw_button_with_text(
+            ctx,
+            state.game_over_restart_rect(),
+            COLOR_PRIMARY,
+            "Restart Game",
+            20.0,
+            false // Not hovered by default
+        )?;
+    }
+
+    // Draw change settings button (back to the menu)
+    draw_button_with_text(
         ctx,
-        &wins_label,
-        DrawParam::default().dest([panel_rect.x + 100.0, panel_rect.y + 210.0]),
+        state.game_over_settings_rect(),
+        COLOR_SECONDARY,
+        if state.restart_locked() { "Quit to Menu" } else { "Change Settings" },
+        18.0,
+        false
     )?;
-    
-    let wins_value = Text::new(
-        TextFragment::new(format!("{}/{}", wins_count, state.past_results.len()))
-            .scale(20.0)
-            .color(COLOR_ACCENT)
+
+    // Seed this game was played with, and a button to play it again
+    let seed_text = Text::new(
+        TextFragment::new(format!("Seed: {}", state.seed))
+            .scale(16.0)
+            .color(COLOR_TEXT)
     );
-    
+
     graphics::draw(
         ctx,
-        &wins_value,
-        DrawParam::default().dest([panel_rect.x + 260.0, panel_rect.y + 210.0]),
+        &seed_text,
+        DrawParam::default().dest([panel_rect.x + 30.0, panel_rect.y + 457.0]),
     )?;
-    
-    // Add win streak info
-    let mut current_streak = 0;
-    let mut best_streak = 0;
-    
-    for &win in state.past_results.iter().rev() {
-        if win {
-            current_streak += 1;
-            best_streak = best_streak.max(current_streak);
-        } else {
-            break;
-        }
-    }
-    
-    let streak_label = Text::new(
-        TextFragment::new("Win Streak: ")
-            .scale(20.0)
-            .color(COLOR_TEXT)
-    );
-    
-    graphics::draw(
+
+    let seed_kept = state.seed_input == state.seed.to_string();
+    draw_button_with_text(
         ctx,
-        &streak_label,
-        DrawParam::default().dest([panel_rect.x + 100.0, panel_rect.y + 250.0]),
+        state.game_over_seed_rect(),
+        if seed_kept { COLOR_DISABLED } else { COLOR_ACCENT },
+        if seed_kept { "Seed Kept" } else { "Replay Seed" },
+        16.0,
+        false
     )?;
-    
-    let streak_value = Text::new(
-        TextFragment::new(format!("{}", best_streak))
-            .scale(20.0)
-            .color(COLOR_ACCENT)
+
+    let (hint, hint_x) = if state.config.sandbox {
+        ("Practice game, not counted in your stats. Press R to restart", center_x - 200.0)
+    } else if state.restart_locked() {
+        ("Hardcore run lost. There's no restarting it", center_x - 140.0)
+    } else {
+        ("Press R to restart", center_x - 60.0)
+    };
+    let restart_hint = Text::new(
+        TextFragment::new(hint)
+            .scale(14.0)
+            .color(COLOR_DISABLED)
     );
-    
+
     graphics::draw(
         ctx,
-        &streak_value,
-        DrawParam::default().dest([panel_rect.x + 260.0, panel_rect.y + 250.0]),
+        &restart_hint,
+        DrawParam::default().dest([hint_x, panel_rect.y + 494.0]),
     )?;
-    
-    // Draw restart button
-    let restart_rect = Rect::new(
-        WINDOW_WIDTH / 2.0 - 75.0,
-        panel_rect.y + 330.0, // Adjusted y position
-        150.0,
-        40.0
+
+    draw_bot_investment(state, ctx, panel_rect)?;
+
+    Ok(())
+}
+
+// What each bot put into upgrades over the game, in a panel beside the game over summary
+fn draw_bot_investment(state: &MainState, ctx: &mut Context, summary_rect: Rect) -> GameResult {
+    let panel_rect = Rect::new(
+        summary_rect.x + summary_rect.w + 20.0,
+        summary_rect.y,
+        300.0,
+        70.0 + state.bots.len() as f32 * 30.0
     );
-    
-    draw_button_with_text(
+
+    // Left out rather than covering the summary when the window is too narrow for it
+    if panel_rect.x + panel_rect.w > state.screen_size.0 {
+        return Ok(());
+    }
+
+    draw_panel(ctx, panel_rect, COLOR_PANEL, 8.0)?;
+
+    draw_header_text(
         ctx,
-        restart_rect,
-        COLOR_PRIMARY,
-        "Restart Game",
+        "Bot upgrades",
+        panel_rect.x + 20.0,
+        panel_rect.y + 15.0,
         20.0,
-        false // Not hovered by default
+        COLOR_PRIMARY
     )?;
 
+    let mut y_offset = panel_rect.y + 50.0;
+
+    for bot in &state.bots {
+        let columns = [
+            (bot.display_name(), if bot.alive { bot.color } else { COLOR_DISABLED }, 20.0),
+            (UpgradeKind::ALL.iter().map(|kind| format!("{}{}", &kind.label()[..1], bot.level(*kind))).collect::<Vec<_>>().join(" "), COLOR_TEXT, 150.0),
+            (format!("{}g", state.format_gold(bot.upgrade_investment())), COLOR_GOLD, 215.0),
+        ];
+
+        for (text, color, offset) in columns {
+            let cell_text = Text::new(
+                TextFragment::new(text)
+                    .scale(16.0)
+                    .color(color)
+            );
+
+            graphics::draw(
+                ctx,
+                &cell_text,
+                DrawParam::default().dest([panel_rect.x + offset, y_offset]),
+            )?;
+        }
+
+        y_offset += 30.0;
+    }
+
     Ok(())
 }